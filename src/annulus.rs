@@ -0,0 +1,138 @@
+use crate::{
+    options::{Options, StrokeOptions},
+    tess, validate,
+    vertex::FillTexCoords,
+    InvalidInput, PolyBuilder, DEFAULT_RADIUS,
+};
+use gee::{Circle, Point, Rect};
+
+/// How texture coordinates are assigned to filled vertices.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum TexCoordMapping {
+    /// Texture coordinates span the bounding rect.
+    #[default]
+    Rect,
+    /// `x` goes clockwise around the center starting from north, and `y` goes from the inner
+    /// edge to the outer edge.
+    ///
+    /// Note: this assumes the texture will use a tiling Sampler.
+    Polar,
+}
+
+/// A ring with a hole in the middle.
+#[derive(Clone, Debug)]
+pub struct AnnulusBuilder {
+    circle: Circle,
+    inner_radius: f32,
+    tex_coord_mapping: TexCoordMapping,
+    options: Options,
+}
+
+impl Default for AnnulusBuilder {
+    fn default() -> Self {
+        Self {
+            circle: Circle::from_radius(DEFAULT_RADIUS),
+            inner_radius: DEFAULT_RADIUS / 2.0,
+            tex_coord_mapping: Default::default(),
+            options: Default::default(),
+        }
+    }
+}
+
+impl AnnulusBuilder {
+    pub fn new(circle: Circle, inner_radius: f32) -> Self {
+        Self::default()
+            .with_circle(circle)
+            .with_inner_radius(inner_radius)
+    }
+
+    /// The circle bounding the outer edge of the ring.
+    pub fn with_circle(mut self, circle: Circle) -> Self {
+        self.circle = circle;
+        self
+    }
+
+    pub fn with_center_and_radius(self, center: Point, radius: f32) -> Self {
+        self.with_circle(Circle::new(center, radius))
+    }
+
+    pub fn with_inner_radius(mut self, inner_radius: f32) -> Self {
+        self.inner_radius = inner_radius;
+        self
+    }
+
+    pub fn with_tex_coord_mapping(mut self, tex_coord_mapping: TexCoordMapping) -> Self {
+        self.tex_coord_mapping = tex_coord_mapping;
+        self
+    }
+
+    stroke!(public);
+
+    fill!();
+
+    build!();
+}
+
+impl PolyBuilder for AnnulusBuilder {
+    fn options(&self) -> &Options {
+        &self.options
+    }
+
+    fn validate(&self) -> Result<(), InvalidInput> {
+        validate::circle(self.circle)?;
+        validate::inner_radius(self.inner_radius, self.circle.radius())
+    }
+
+    fn bounding_rect(&self) -> Rect {
+        self.circle.bounding_rect()
+    }
+
+    fn fill_tex_coords(&self) -> FillTexCoords {
+        match self.tex_coord_mapping {
+            TexCoordMapping::Rect => FillTexCoords::new(self.bounding_rect()),
+            TexCoordMapping::Polar => FillTexCoords::polar(
+                self.circle.center(),
+                self.inner_radius,
+                self.circle.radius(),
+            ),
+        }
+    }
+
+    fn build<B: tess::path::traits::PathBuilder>(self, builder: &mut B) {
+        let center = self.circle.center().into();
+        builder.add_circle(center, self.circle.radius(), tess::path::Winding::Positive);
+        builder.add_circle(center, self.inner_radius, tess::path::Winding::Negative);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AnnulusBuilder, Error, InvalidInput, TexCoordMapping};
+    use gee::Circle;
+
+    #[test]
+    fn inner_radius_inside_radius() {
+        for inner_radius in [10.0, 20.0] {
+            let result = AnnulusBuilder::new(Circle::from_radius(10.0), inner_radius)
+                .with_tex_coord_mapping(TexCoordMapping::Polar)
+                .try_build();
+            assert!(matches!(
+                result,
+                Err(Error::InvalidInput {
+                    source: InvalidInput::OutOfRange {
+                        name: "inner_radius",
+                        ..
+                    },
+                    ..
+                })
+            ));
+        }
+        let poly = AnnulusBuilder::new(Circle::from_radius(10.0), 5.0)
+            .with_tex_coord_mapping(TexCoordMapping::Polar)
+            .build();
+        assert!(poly
+            .vertices
+            .iter()
+            .all(|vertex| (-0.01..=1.01).contains(&vertex.tex_coord.y)));
+    }
+}
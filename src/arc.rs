@@ -0,0 +1,122 @@
+use crate::{
+    default_start_angle,
+    options::{Options, StrokeOptions},
+    tess, validate, InvalidInput, PolyBuilder, DEFAULT_RADIUS,
+};
+use gee::{Angle, Circle, Point, Rect};
+
+pub(crate) fn arc(circle: Circle, start_angle: Angle, sweep: Angle) -> tess::geom::Arc<f32> {
+    tess::geom::Arc {
+        center: circle.center().into(),
+        radii: tess::math::vector(circle.radius(), circle.radius()),
+        start_angle: tess::math::Angle::radians(start_angle.radians()),
+        sweep_angle: tess::math::Angle::radians(sweep.radians()),
+        x_rotation: tess::math::Angle::zero(),
+    }
+}
+
+pub(crate) fn arc_to<B: tess::path::traits::PathBuilder>(
+    builder: &mut B,
+    arc: &tess::geom::Arc<f32>,
+) {
+    arc.for_each_quadratic_bezier(&mut |curve| {
+        builder.quadratic_bezier_to(curve.ctrl, curve.to);
+    });
+}
+
+pub(crate) fn default_sweep() -> Angle {
+    Angle::PI() / 2.0
+}
+
+/// An open circular arc, for progress rings and the like.
+#[derive(Clone, Debug)]
+pub struct ArcBuilder {
+    circle: Circle,
+    start_angle: Angle,
+    sweep: Angle,
+    options: Options,
+}
+
+impl Default for ArcBuilder {
+    fn default() -> Self {
+        Self {
+            circle: Circle::from_radius(DEFAULT_RADIUS),
+            start_angle: default_start_angle(),
+            sweep: default_sweep(),
+            options: Options::default().with_stroke_opts(StrokeOptions::default()),
+        }
+    }
+}
+
+impl ArcBuilder {
+    pub fn new(circle: Circle, sweep: impl Into<Angle>) -> Self {
+        Self::default().with_circle(circle).with_sweep(sweep)
+    }
+
+    pub fn with_circle(mut self, circle: Circle) -> Self {
+        self.circle = circle;
+        self
+    }
+
+    pub fn with_center_and_radius(self, center: Point, radius: f32) -> Self {
+        self.with_circle(Circle::new(center, radius))
+    }
+
+    pub fn with_rotation(mut self, start_angle: impl Into<Angle>) -> Self {
+        self.start_angle = start_angle.into();
+        self
+    }
+
+    /// How far the arc extends from its start angle.
+    ///
+    /// Positive sweeps go clockwise.
+    pub fn with_sweep(mut self, sweep: impl Into<Angle>) -> Self {
+        self.sweep = sweep.into();
+        self
+    }
+
+    stroke!(public);
+
+    build!();
+}
+
+impl PolyBuilder for ArcBuilder {
+    fn options(&self) -> &Options {
+        &self.options
+    }
+
+    fn validate(&self) -> Result<(), InvalidInput> {
+        validate::circle(self.circle)?;
+        validate::angle("start_angle", self.start_angle)?;
+        validate::angle("sweep", self.sweep)
+    }
+
+    fn bounding_rect(&self) -> Rect {
+        self.circle.bounding_rect()
+    }
+
+    fn build<B: tess::path::traits::PathBuilder>(self, builder: &mut B) {
+        let arc = arc(self.circle, self.start_angle, self.sweep);
+        builder.begin(arc.from());
+        arc_to(builder, &arc);
+        builder.end(false);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ArcBuilder;
+
+    #[test]
+    fn default_has_visible_stroke() {
+        let poly = ArcBuilder::default().build();
+        let (min, max) = poly
+            .vertices
+            .iter()
+            .fold((f32::MAX, f32::MIN), |(min, max), vertex| {
+                let distance = vertex.pos.x.hypot(vertex.pos.y);
+                (min.min(distance), max.max(distance))
+            });
+        assert!(max - min > 0.5, "{} {}", min, max);
+    }
+}
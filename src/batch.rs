@@ -0,0 +1,155 @@
+use crate::{
+    tess, DefaultVertexConstructor, Error, Poly, PolyBuilder, Tessellator, VertexConstructor,
+};
+use std::ops::Range;
+
+/// Where a shape's triangles ended up in a `Batch`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BatchItem {
+    /// The range of `Batch::indices` making up the fill, if any.
+    pub fill_indices: Range<usize>,
+    /// The range of `Batch::indices` making up the stroke, if any.
+    pub stroke_indices: Range<usize>,
+}
+
+impl BatchItem {
+    /// The range of `Batch::indices` making up the whole shape.
+    pub fn indices(&self) -> Range<usize> {
+        self.fill_indices.start..self.stroke_indices.end
+    }
+}
+
+/// Tessellates many shapes into one set of vertices and indices, for drawing them all at once.
+///
+/// Add shapes with `add_to` or `try_add_to` on any builder. Use `clear` to start over while
+/// keeping the allocations.
+pub struct Batch<C: VertexConstructor = DefaultVertexConstructor> {
+    tessellator: Tessellator<C>,
+    buf: tess::VertexBuffers<C::Vertex, u32>,
+    items: Vec<BatchItem>,
+}
+
+impl Batch {
+    pub fn new() -> Self {
+        Self::with_constructor(DefaultVertexConstructor)
+    }
+}
+
+impl Default for Batch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: VertexConstructor> Batch<C> {
+    /// Creates a `Batch` with vertices produced by `constructor`.
+    pub fn with_constructor(constructor: C) -> Self {
+        Self {
+            tessellator: Tessellator::with_constructor(constructor),
+            buf: tess::VertexBuffers::new(),
+            items: Vec::new(),
+        }
+    }
+
+    pub(crate) fn try_add<T: PolyBuilder>(&mut self, poly: T) -> Result<usize, Error> {
+        let (vertex_count, index_count) = (self.buf.vertices.len(), self.buf.indices.len());
+        match Poly::tessellate(poly, &mut self.tessellator, &mut self.buf) {
+            Ok(item) => {
+                self.items.push(item);
+                Ok(self.items.len() - 1)
+            }
+            Err(err) => {
+                // don't leave a partial shape behind
+                self.buf.vertices.truncate(vertex_count);
+                self.buf.indices.truncate(index_count);
+                Err(err)
+            }
+        }
+    }
+
+    pub fn vertices(&self) -> &[C::Vertex] {
+        &self.buf.vertices
+    }
+
+    pub fn indices(&self) -> &[u32] {
+        &self.buf.indices
+    }
+
+    /// One item per shape, in the order they were added.
+    pub fn items(&self) -> &[BatchItem] {
+        &self.items
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Removes every shape, keeping the allocations around for reuse.
+    pub fn clear(&mut self) {
+        self.buf.vertices.clear();
+        self.buf.indices.clear();
+        self.items.clear();
+    }
+
+    /// Consumes the `Batch`, returning its vertices, indices and items.
+    pub fn into_parts(self) -> (Vec<C::Vertex>, Vec<u32>, Vec<BatchItem>) {
+        (self.buf.vertices, self.buf.indices, self.items)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CircleBuilder, FreePolyBuilder, RoundRectBuilder};
+    use gee::{Circle, Point};
+
+    #[test]
+    fn shapes_share_buffers() {
+        let mut batch = Batch::new();
+        let square = RoundRectBuilder::default().with_fill_and_stroke(2.0);
+        let circle = CircleBuilder::new(Circle::from_radius(10.0));
+        assert_eq!(square.clone().add_to(&mut batch), 0);
+        assert_eq!(circle.clone().add_to(&mut batch), 1);
+        assert_eq!(square.clone().add_to(&mut batch), 2);
+
+        let items = batch.items().to_vec();
+        let parts = [square.clone().build(), circle.build(), square.build()];
+        let mut vertex_offset = 0;
+        let mut index_offset = 0;
+        for (item, poly) in items.iter().zip(&parts) {
+            let offset =
+                |range: &Range<usize>| range.start + index_offset..range.end + index_offset;
+            assert_eq!(item.fill_indices, offset(&poly.fill_indices));
+            assert_eq!(item.stroke_indices, offset(&poly.stroke_indices));
+            // indices point at this shape's own vertices
+            assert!(batch.indices()[item.indices()]
+                .iter()
+                .zip(&poly.indices)
+                .all(|(&index, &own)| index as usize == own as usize + vertex_offset));
+            vertex_offset += poly.vertices.len();
+            index_offset += poly.indices.len();
+        }
+        assert_eq!(batch.vertices().len(), vertex_offset);
+        assert_eq!(batch.indices().len(), index_offset);
+    }
+
+    #[test]
+    fn failed_add_leaves_batch_unchanged() {
+        let mut batch = Batch::new();
+        RoundRectBuilder::default().add_to(&mut batch);
+        let (vertex_count, index_count) = (batch.vertices().len(), batch.indices().len());
+        // straight edges, so this fails in the fill tessellator instead of panicking
+        let broken = FreePolyBuilder::from_points([
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(0.0, 10.0),
+        ])
+        .with_fill_and_stroke(2.0)
+        .with_tolerance(f32::NAN);
+        assert!(broken.try_add_to(&mut batch).is_err());
+        assert_eq!(batch.vertices().len(), vertex_count);
+        assert_eq!(batch.indices().len(), index_count);
+        assert_eq!(batch.items().len(), 1);
+        assert_eq!(RoundRectBuilder::default().add_to(&mut batch), 1);
+    }
+}
@@ -1,9 +1,12 @@
 use crate::{
     options::{Options, StrokeOptions},
-    tess, PolyBuilder,
+    path::union_boxes,
+    tess, validate, InvalidInput, PolyBuilder,
 };
 use gee::{Point, Rect};
 
+type Box2D = tess::geom::euclid::default::Box2D<f32>;
+
 #[derive(Clone, Debug)]
 pub enum ControlPoint {
     Quadratic(Point),
@@ -126,8 +129,46 @@ impl PolyBuilder for BezierBuilder {
         &self.options
     }
 
+    fn validate(&self) -> Result<(), InvalidInput> {
+        validate::point("start", self.start)?;
+        self.segments.iter().try_for_each(|segment| {
+            validate::point("end", segment.end)?;
+            match segment.ctrl {
+                ControlPoint::Quadratic(ctrl) => validate::point("ctrl", ctrl),
+                ControlPoint::Cubic(ctrl1, ctrl2) => {
+                    validate::point("ctrl1", ctrl1)?;
+                    validate::point("ctrl2", ctrl2)
+                }
+            }
+        })
+    }
+
     fn bounding_rect(&self) -> Rect {
-        todo!("bézier curves can't be filled")
+        let mut from = tess::math::Point::from(self.start);
+        let bounding_box =
+            self.segments
+                .iter()
+                .fold(Box2D::new(from, from), |bounding_box, segment| {
+                    let to = segment.end.into();
+                    let segment_box = match segment.ctrl {
+                        ControlPoint::Quadratic(ctrl) => tess::geom::QuadraticBezierSegment {
+                            from,
+                            ctrl: ctrl.into(),
+                            to,
+                        }
+                        .bounding_box(),
+                        ControlPoint::Cubic(ctrl1, ctrl2) => tess::geom::CubicBezierSegment {
+                            from,
+                            ctrl1: ctrl1.into(),
+                            ctrl2: ctrl2.into(),
+                            to,
+                        }
+                        .bounding_box(),
+                    };
+                    from = to;
+                    union_boxes(&bounding_box, &segment_box)
+                });
+        Rect::from_points(bounding_box.min.into(), bounding_box.max.into())
     }
 
     fn build<B: tess::path::traits::PathBuilder>(self, builder: &mut B) {
@@ -0,0 +1,444 @@
+use crate::{
+    flatten::{flatten, Contour},
+    tess, FillRule, PathBuilder, PolyBuilder as _, Transform,
+};
+use std::collections::HashMap;
+
+type Point = tess::math::Point;
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum BooleanOp {
+    Union,
+    Intersection,
+    Difference,
+    Xor,
+}
+
+impl BooleanOp {
+    fn apply(self, a: bool, b: bool) -> bool {
+        match self {
+            Self::Union => a || b,
+            Self::Intersection => a && b,
+            Self::Difference => a && !b,
+            Self::Xor => a != b,
+        }
+    }
+}
+
+/// Boolean operations between shapes.
+///
+/// Both operands are flattened, with their own tolerance, transform and fill rule, so the result
+/// is made of straight lines in output space. It keeps the options of `self`, apart from having no
+/// transform and using `FillRule::NonZero`. Any shape can take part by converting it with `to_path`.
+///
+/// An empty result, such as the intersection of shapes that don't overlap, fails to fill.
+/// Every edge is tested against every other, so this gets slow for shapes with thousands of edges.
+impl PathBuilder {
+    /// The area covered by either shape.
+    pub fn union(self, other: PathBuilder) -> Self {
+        self.boolean(other, BooleanOp::Union)
+    }
+
+    /// The area covered by both shapes.
+    pub fn intersection(self, other: PathBuilder) -> Self {
+        self.boolean(other, BooleanOp::Intersection)
+    }
+
+    /// The area covered by `self` but not by `other`.
+    pub fn difference(self, other: PathBuilder) -> Self {
+        self.boolean(other, BooleanOp::Difference)
+    }
+
+    /// The area covered by exactly one of the shapes.
+    pub fn xor(self, other: PathBuilder) -> Self {
+        self.boolean(other, BooleanOp::Xor)
+    }
+
+    fn boolean(self, other: PathBuilder, op: BooleanOp) -> Self {
+        let mut options = self.options().clone();
+        let a = Shape::new(self);
+        let b = Shape::new(other);
+        options.transform = Transform::identity();
+        options.fill_rule = FillRule::NonZero;
+        PathBuilder::from_polygons(combine(&a, &b, op), options)
+    }
+}
+
+/// A flattened shape, filled according to `fill_rule`.
+pub(crate) struct Shape {
+    pub(crate) contours: Vec<Contour>,
+    pub(crate) fill_rule: FillRule,
+}
+
+impl Shape {
+    pub(crate) fn new(path: PathBuilder) -> Self {
+        let fill_rule = path.options().fill_rule;
+        Self {
+            contours: flatten(path),
+            fill_rule,
+        }
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (Point, Point)> + '_ {
+        self.contours.iter().flat_map(|contour| {
+            contour
+                .iter()
+                .zip(contour.iter().cycle().skip(1))
+                .map(|(&from, &to)| (from, to))
+        })
+    }
+
+    /// Whether the shape is filled just left and just right of `mid`, which lies on an edge going
+    /// in the unit `direction`.
+    ///
+    /// We count crossings along a ray from `mid` towards the left, so unlike testing points
+    /// offset from the edge, this works no matter how thin the shape is. Edges within `epsilon`
+    /// of `mid` overlap the edge itself, and only count towards the right side.
+    fn sides(&self, mid: Point, direction: tess::math::Vector, epsilon: f32) -> (bool, bool) {
+        let normal = tess::math::vector(-direction.y, direction.x);
+        let (mut left, mut right) = (0, 0);
+        for (from, to) in self.edges() {
+            let edge = to - from;
+            let along = (mid - from).dot(edge) / edge.square_length();
+            if (0.0..=1.0).contains(&along)
+                && edge.cross(mid - from).abs() <= epsilon * edge.length()
+            {
+                right += if edge.dot(direction) > 0.0 { -1 } else { 1 };
+                continue;
+            }
+            let (x0, x1) = ((from - mid).dot(direction), (to - mid).dot(direction));
+            if (x0 > 0.0) != (x1 > 0.0) {
+                let (y0, y1) = ((from - mid).dot(normal), (to - mid).dot(normal));
+                if y0 + (y1 - y0) * (x0 / (x0 - x1)) > 0.0 {
+                    let winding = if x1 > x0 { -1 } else { 1 };
+                    left += winding;
+                    right += winding;
+                }
+            }
+        }
+        (self.fills(left), self.fills(right))
+    }
+
+    fn fills(&self, winding: i32) -> bool {
+        match self.fill_rule {
+            FillRule::EvenOdd => winding % 2 != 0,
+            FillRule::NonZero => winding != 0,
+        }
+    }
+}
+
+fn key(point: Point) -> (u32, u32) {
+    (point.x.to_bits(), point.y.to_bits())
+}
+
+/// Combines the edges of both shapes, keeping those that separate the result from the outside.
+///
+/// Edges are split wherever they cross, so each piece is either entirely on the boundary of the
+/// result or not at all. The pieces we keep have the inside of the result on their left, which
+/// lets us chain them back into closed polygons.
+pub(crate) fn combine(a: &Shape, b: &Shape, op: BooleanOp) -> Vec<Contour> {
+    let edges = a
+        .edges()
+        .chain(b.edges())
+        .filter(|(from, to)| from != to)
+        .collect::<Vec<_>>();
+    let extent = edges
+        .iter()
+        .flat_map(|&(from, to)| [from, to])
+        .map(|point| point.x.abs().max(point.y.abs()))
+        .fold(1.0f32, f32::max);
+    let epsilon = extent * 1e-6;
+    let weld_radius = extent * 2e-6;
+    let mut welder = Welder::new(weld_radius);
+
+    let mut kept = Vec::new();
+    for (edge, points) in edges.iter().zip(split_points(&edges, epsilon)) {
+        let (from, to) = *edge;
+        let mut points = points
+            .into_iter()
+            .map(|point| ((point - from).dot(to - from), point))
+            .collect::<Vec<_>>();
+        points.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+        let points = std::iter::once(from)
+            .chain(points.into_iter().map(|(_, point)| point))
+            .chain(std::iter::once(to))
+            .map(|point| welder.weld(point))
+            .collect::<Vec<_>>();
+        for (&from, &to) in points.iter().zip(&points[1..]) {
+            if from == to {
+                continue;
+            }
+            let direction = (to - from).normalize();
+            let mid = from.lerp(to, 0.5);
+            let (a_left, a_right) = a.sides(mid, direction, weld_radius);
+            let (b_left, b_right) = b.sides(mid, direction, weld_radius);
+            match (op.apply(a_left, b_left), op.apply(a_right, b_right)) {
+                (true, false) => kept.push((from, to)),
+                (false, true) => kept.push((to, from)),
+                _ => {}
+            }
+        }
+    }
+    kept.sort_by_key(|&(from, to)| (key(from), key(to)));
+    kept.dedup();
+    chain(kept)
+}
+
+/// Merges points closer than `radius` together.
+///
+/// Crossings between nearly parallel edges are imprecise, so several edges crossing at one point
+/// rarely agree on exactly where. Without merging them, the tiny pieces between them leave gaps
+/// when chaining.
+struct Welder {
+    radius: f32,
+    cells: HashMap<(i64, i64), Vec<Point>>,
+}
+
+impl Welder {
+    fn new(radius: f32) -> Self {
+        Self {
+            radius,
+            cells: HashMap::new(),
+        }
+    }
+
+    fn weld(&mut self, point: Point) -> Point {
+        let cell = (
+            (point.x / self.radius).floor() as i64,
+            (point.y / self.radius).floor() as i64,
+        );
+        let nearby = (-1..=1)
+            .flat_map(|dx| (-1..=1).map(move |dy| (cell.0 + dx, cell.1 + dy)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .copied()
+            .find(|&other| (other - point).length() <= self.radius);
+        nearby.unwrap_or_else(|| {
+            self.cells.entry(cell).or_default().push(point);
+            point
+        })
+    }
+}
+
+/// Finds where each edge crosses or touches the others, excluding its own endpoints.
+///
+/// Each crossing is computed once and shared by both edges, so that the pieces they're split into
+/// meet exactly.
+fn split_points(edges: &[(Point, Point)], epsilon: f32) -> Vec<Vec<Point>> {
+    let mut splits = vec![Vec::new(); edges.len()];
+    let add = |splits: &mut Vec<Vec<Point>>, index: usize, point: Point| {
+        let (from, to) = edges[index];
+        if point != from && point != to {
+            splits[index].push(point);
+        }
+    };
+    for (i, &(p, p_end)) in edges.iter().enumerate() {
+        let r = p_end - p;
+        for (j, &(q, q_end)) in edges.iter().enumerate().skip(i + 1) {
+            let s = q_end - q;
+            let denominator = r.cross(s);
+            let t_epsilon = epsilon / r.length();
+            let u_epsilon = epsilon / s.length();
+            if denominator.abs() > f32::EPSILON * r.length() * s.length() {
+                let t = (q - p).cross(s) / denominator;
+                let u = (q - p).cross(r) / denominator;
+                let range = |t: f32, epsilon: f32| -epsilon <= t && t <= 1.0 + epsilon;
+                if !range(t, t_epsilon) || !range(u, u_epsilon) {
+                    continue;
+                }
+                let point = if t.abs() <= t_epsilon {
+                    p
+                } else if (t - 1.0).abs() <= t_epsilon {
+                    p_end
+                } else if u.abs() <= u_epsilon {
+                    q
+                } else if (u - 1.0).abs() <= u_epsilon {
+                    q_end
+                } else {
+                    p + r * t
+                };
+                add(&mut splits, i, point);
+                add(&mut splits, j, point);
+            } else if (r.cross(q - p) / r.length()).abs() <= epsilon {
+                // Overlapping edges split each other at their endpoints.
+                for point in [q, q_end] {
+                    let t = (point - p).dot(r) / r.square_length();
+                    if t_epsilon < t && t < 1.0 - t_epsilon {
+                        add(&mut splits, i, point);
+                    }
+                }
+                for point in [p, p_end] {
+                    let u = (point - q).dot(s) / s.square_length();
+                    if u_epsilon < u && u < 1.0 - u_epsilon {
+                        add(&mut splits, j, point);
+                    }
+                }
+            }
+        }
+    }
+    splits
+}
+
+/// Links directed edges end to start into closed polygons.
+fn chain(edges: Vec<(Point, Point)>) -> Vec<Contour> {
+    let mut starting_at = HashMap::<_, Vec<usize>>::new();
+    for (index, &(from, _)) in edges.iter().enumerate() {
+        starting_at.entry(key(from)).or_default().push(index);
+    }
+    let mut used = vec![false; edges.len()];
+    let mut polygons = Vec::new();
+    for first in 0..edges.len() {
+        if used[first] {
+            continue;
+        }
+        let mut polygon = Vec::new();
+        let mut current = Some(first);
+        while let Some(index) = current {
+            used[index] = true;
+            let (from, to) = edges[index];
+            polygon.push(from);
+            current = starting_at
+                .get(&key(to))
+                .and_then(|next| next.iter().copied().find(|&next| !used[next]));
+        }
+        if polygon.len() >= 3 {
+            polygons.push(polygon);
+        }
+    }
+    polygons
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{flatten::signed_area, Error, InvalidInput, RegularPolyBuilder};
+    use gee::Point;
+
+    fn rect(left: f32, top: f32, right: f32, bottom: f32) -> PathBuilder {
+        PathBuilder::new()
+            .move_to(Point::new(left, top))
+            .line_to(Point::new(right, top))
+            .line_to(Point::new(right, bottom))
+            .line_to(Point::new(left, bottom))
+            .close()
+    }
+
+    /// The area of each contour, with holes negative.
+    fn areas(path: PathBuilder) -> Vec<f32> {
+        flatten(path)
+            .iter()
+            .map(|contour| signed_area(contour))
+            .collect()
+    }
+
+    fn area(path: PathBuilder) -> f32 {
+        areas(path).into_iter().sum()
+    }
+
+    fn assert_area(path: PathBuilder, expected: f32) {
+        let area = area(path);
+        assert!((area - expected).abs() < 1e-3, "{area} != {expected}");
+    }
+
+    #[test]
+    fn overlapping() {
+        let a = || rect(0.0, 0.0, 10.0, 10.0);
+        let b = || rect(5.0, 5.0, 15.0, 15.0);
+        assert_area(a().union(b()), 175.0);
+        assert_area(a().intersection(b()), 25.0);
+        assert_area(a().difference(b()), 75.0);
+        assert_area(b().difference(a()), 75.0);
+        assert_area(a().xor(b()), 150.0);
+        assert_eq!(areas(a().union(b())).len(), 1);
+    }
+
+    #[test]
+    fn difference_leaves_hole() {
+        let areas = areas(rect(0.0, 0.0, 30.0, 30.0).difference(rect(10.0, 10.0, 20.0, 20.0)));
+        assert_eq!(areas.len(), 2);
+        assert_eq!(areas.iter().filter(|&&area| area < 0.0).count(), 1);
+        assert!((areas.iter().sum::<f32>() - 800.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn xor_of_nested_shapes_is_a_ring() {
+        let areas = areas(rect(0.0, 0.0, 30.0, 30.0).xor(rect(10.0, 10.0, 20.0, 20.0)));
+        assert_eq!(areas.len(), 2);
+        assert!((areas.iter().sum::<f32>() - 800.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn disjoint() {
+        let a = || rect(0.0, 0.0, 10.0, 10.0);
+        let b = || rect(20.0, 0.0, 30.0, 10.0);
+        assert_area(a().union(b()), 200.0);
+        assert_eq!(areas(a().union(b())).len(), 2);
+        assert!(areas(a().intersection(b())).is_empty());
+        assert_area(a().difference(b()), 100.0);
+        assert_area(a().xor(b()), 200.0);
+        assert!(a().intersection(b()).try_build().is_err());
+    }
+
+    #[test]
+    fn shared_edges() {
+        let a = || rect(0.0, 0.0, 10.0, 10.0);
+        let b = || rect(10.0, 0.0, 20.0, 10.0);
+        assert_area(a().union(b()), 200.0);
+        assert_eq!(areas(a().union(b())).len(), 1);
+        assert!(areas(a().intersection(b())).is_empty());
+        assert_area(a().difference(b()), 100.0);
+        assert!(areas(a().union(a()).difference(a())).is_empty());
+    }
+
+    #[test]
+    fn collinear_edges() {
+        let a = || rect(0.0, 0.0, 10.0, 10.0);
+        let b = || rect(5.0, 0.0, 15.0, 10.0);
+        assert_area(a().union(b()), 150.0);
+        assert_eq!(areas(a().union(b())).len(), 1);
+        assert_area(a().intersection(b()), 50.0);
+        assert_area(a().difference(b()), 50.0);
+        assert_area(a().xor(b()), 100.0);
+    }
+
+    #[test]
+    fn thin_shapes() {
+        let a = || rect(0.0, 0.0, 10.0, 10.0);
+        let sliver = || rect(-5.0, 4.0, 15.0, 4.001);
+        assert_area(a().union(sliver()), 100.01);
+        assert_area(a().intersection(sliver()), 0.01);
+        assert_eq!(areas(a().difference(sliver())).len(), 2);
+    }
+
+    #[test]
+    fn fill_rules() {
+        let twice = || {
+            rect(0.0, 0.0, 10.0, 10.0)
+                .move_to(Point::new(0.0, 0.0))
+                .line_to(Point::new(10.0, 0.0))
+                .line_to(Point::new(10.0, 10.0))
+                .line_to(Point::new(0.0, 10.0))
+                .close()
+        };
+        let other = || rect(20.0, 0.0, 30.0, 10.0);
+        assert_area(
+            twice().with_fill_rule(FillRule::NonZero).union(other()),
+            200.0,
+        );
+        assert_area(
+            twice().with_fill_rule(FillRule::EvenOdd).union(other()),
+            100.0,
+        );
+    }
+
+    #[test]
+    fn invalid_shape() {
+        assert!(matches!(
+            RegularPolyBuilder::new(2).to_path(),
+            Err(Error::InvalidInput {
+                builder: "RegularPolyBuilder",
+                source: InvalidInput::SideCount { sides: 2, min: 3 },
+            })
+        ));
+    }
+}
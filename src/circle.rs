@@ -1,6 +1,6 @@
 use crate::{
     options::{Options, StrokeOptions},
-    tess, PolyBuilder,
+    tess, validate, InvalidInput, PolyBuilder,
 };
 use gee::{Circle, Rect};
 
@@ -32,6 +32,10 @@ impl PolyBuilder for CircleBuilder {
         &self.options
     }
 
+    fn validate(&self) -> Result<(), InvalidInput> {
+        validate::circle(self.circle)
+    }
+
     fn bounding_rect(&self) -> Rect {
         self.circle.bounding_rect()
     }
@@ -0,0 +1,435 @@
+use crate::{
+    tess::{
+        self,
+        geom::{CubicBezierSegment, QuadraticBezierSegment},
+        path::EndpointId,
+    },
+    validate, InvalidInput,
+};
+use std::{cell::Cell, rc::Rc};
+
+/// Alternating lengths of dashes and gaps, starting with a dash.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DashPattern {
+    pub lengths: Vec<f32>,
+    pub offset: f32,
+}
+
+impl DashPattern {
+    pub fn new(lengths: impl IntoIterator<Item = f32>) -> Self {
+        Self {
+            lengths: lengths.into_iter().collect(),
+            offset: 0.0,
+        }
+    }
+
+    pub fn dashed(dash: f32, gap: f32) -> Self {
+        Self::new([dash, gap])
+    }
+
+    /// Zero-length dashes spaced `spacing` apart.
+    ///
+    /// Note: the dots are only visible with `LineCap::Round` or `LineCap::Square`.
+    pub fn dotted(spacing: f32) -> Self {
+        Self::new([0.0, spacing])
+    }
+
+    /// How far into the pattern each sub-path starts.
+    pub fn with_offset(mut self, offset: f32) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    fn period(&self) -> f32 {
+        // Odd-length patterns repeat twice to keep dashes and gaps alternating.
+        let sum = self.lengths.iter().sum::<f32>();
+        if self.lengths.len().is_multiple_of(2) {
+            sum
+        } else {
+            sum * 2.0
+        }
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), InvalidInput> {
+        self.lengths
+            .iter()
+            .try_for_each(|&length| validate::non_negative("dash length", length))?;
+        validate::finite("dash offset", self.offset)?;
+        validate::positive("dash pattern period", self.period())
+    }
+}
+
+/// Splits every sub-path into dashes before forwarding them to `inner`.
+///
+/// Stroke advancement only accumulates along the dashes themselves, so the
+/// total length of the gaps skipped so far is published through
+/// `advancement_offset` to keep texture coordinates continuous.
+///
+/// Each sub-path is flattened and held back until it ends, since a closed
+/// sub-path that starts on a dash is dashed from where that dash ends instead.
+/// That way the dash crossing the start point is drawn in one piece, rather
+/// than as two halves with caps in between.
+pub(crate) struct Dasher<'a, B> {
+    inner: &'a mut B,
+    pattern: &'a DashPattern,
+    tolerance: f32,
+    advancement_offset: Rc<Cell<f32>>,
+    index: usize,
+    remaining: f32,
+    points: Vec<tess::math::Point>,
+    current: tess::math::Point,
+    pen_down: bool,
+    next_endpoint_id: u32,
+}
+
+impl<'a, B: tess::path::traits::PathBuilder> Dasher<'a, B> {
+    pub fn new(
+        inner: &'a mut B,
+        pattern: &'a DashPattern,
+        tolerance: f32,
+        advancement_offset: Rc<Cell<f32>>,
+    ) -> Self {
+        debug_assert!(pattern.validate().is_ok());
+        Self {
+            inner,
+            pattern,
+            tolerance,
+            advancement_offset,
+            index: 0,
+            remaining: 0.0,
+            points: Vec::new(),
+            current: tess::math::point(0.0, 0.0),
+            pen_down: false,
+            next_endpoint_id: 0,
+        }
+    }
+
+    fn endpoint_id(&mut self) -> EndpointId {
+        let id = EndpointId(self.next_endpoint_id);
+        self.next_endpoint_id += 1;
+        id
+    }
+
+    fn length(&self, index: usize) -> f32 {
+        self.pattern.lengths[index % self.pattern.lengths.len()]
+    }
+
+    fn is_dash(&self) -> bool {
+        self.index.is_multiple_of(2)
+    }
+
+    fn skip_gap(&mut self, length: f32) {
+        self.advancement_offset
+            .set(self.advancement_offset.get() + length);
+    }
+
+    /// Starts the pattern over, `offset` into it.
+    fn restart(&mut self) {
+        self.index = 0;
+        self.remaining = self.length(0);
+        let mut offset = self.pattern.offset.rem_euclid(self.pattern.period());
+        while offset > 0.0 && offset >= self.remaining {
+            offset -= self.remaining;
+            self.index += 1;
+            self.remaining = self.length(self.index);
+        }
+        self.remaining -= offset;
+    }
+
+    fn step(&mut self) {
+        self.index = (self.index + 1) % (self.pattern.lengths.len() * 2);
+        self.remaining = self.length(self.index);
+    }
+
+    fn advance(&mut self) {
+        if self.is_dash() {
+            self.inner.end(false);
+            self.pen_down = false;
+        } else {
+            self.inner.begin(self.current);
+            self.pen_down = true;
+        }
+        self.step();
+    }
+
+    /// Follows the pattern to `to`. With `leave_open`, a dash ending exactly at
+    /// `to` is left open, so that it can carry on into the first dash.
+    fn dash_to(&mut self, to: tess::math::Point, leave_open: bool) {
+        let mut left = (to - self.current).length();
+        while self.remaining < left || (self.remaining == left && !leave_open) {
+            let step = self.remaining;
+            let from = self.current;
+            self.current = if step < left {
+                from.lerp(to, step / left)
+            } else {
+                to
+            };
+            left -= step;
+            if step > 0.0 {
+                if self.is_dash() {
+                    self.inner.line_to(self.current);
+                } else {
+                    self.skip_gap(step);
+                }
+            }
+            self.advance();
+        }
+        self.remaining -= left;
+        if left > 0.0 {
+            if self.is_dash() {
+                self.inner.line_to(to);
+            } else {
+                self.skip_gap(left);
+            }
+        }
+        self.current = to;
+    }
+
+    fn dash_open(&mut self, points: &[tess::math::Point]) {
+        self.current = points[0];
+        if self.is_dash() {
+            self.inner.begin(self.current);
+            self.pen_down = true;
+        }
+        for &point in &points[1..] {
+            self.dash_to(point, false);
+        }
+        if self.pen_down {
+            self.inner.end(false);
+            self.pen_down = false;
+        }
+    }
+
+    /// Dashes a sub-path starting on a dash, where the last point is the first.
+    fn dash_closed(&mut self, points: &[tess::math::Point]) {
+        let first_dash = self.remaining;
+        let mut travelled = 0.0;
+        let split = points.windows(2).enumerate().find_map(|(index, edge)| {
+            let length = (edge[1] - edge[0]).length();
+            if length > 0.0 && travelled + length >= first_dash {
+                Some((
+                    index,
+                    edge[0].lerp(edge[1], (first_dash - travelled) / length),
+                ))
+            } else {
+                travelled += length;
+                None
+            }
+        });
+        let (split, first_dash_end) = match split {
+            Some(split) => split,
+            None => {
+                // the first dash goes all the way around
+                self.inner.begin(points[0]);
+                for &point in &points[1..points.len() - 1] {
+                    self.inner.line_to(point);
+                }
+                self.inner.end(true);
+                return;
+            }
+        };
+
+        // The first dash is drawn last, continuing whatever reaches the start
+        // point, so its length is skipped over until then.
+        self.skip_gap(first_dash);
+        self.step();
+        self.current = first_dash_end;
+        for &point in &points[split + 1..points.len() - 1] {
+            self.dash_to(point, false);
+        }
+        self.dash_to(points[0], true);
+        if !self.pen_down {
+            self.inner.begin(points[0]);
+        }
+        if first_dash > 0.0 {
+            for &point in &points[1..=split] {
+                self.inner.line_to(point);
+            }
+            self.inner.line_to(first_dash_end);
+        }
+        self.inner.end(false);
+        self.pen_down = false;
+        self.skip_gap(-first_dash);
+    }
+}
+
+impl<'a, B: tess::path::traits::PathBuilder> tess::path::traits::PathBuilder for Dasher<'a, B> {
+    fn begin(&mut self, at: tess::math::Point) -> EndpointId {
+        self.points.clear();
+        self.points.push(at);
+        self.current = at;
+        self.endpoint_id()
+    }
+
+    fn end(&mut self, close: bool) {
+        if self.points.is_empty() {
+            return;
+        }
+        let mut points = std::mem::take(&mut self.points);
+        if close {
+            points.push(points[0]);
+        }
+        self.restart();
+        if close && self.is_dash() {
+            self.dash_closed(&points);
+        } else {
+            self.dash_open(&points);
+        }
+        points.clear();
+        self.points = points;
+    }
+
+    fn line_to(&mut self, to: tess::math::Point) -> EndpointId {
+        self.points.push(to);
+        self.current = to;
+        self.endpoint_id()
+    }
+
+    fn quadratic_bezier_to(
+        &mut self,
+        ctrl: tess::math::Point,
+        to: tess::math::Point,
+    ) -> EndpointId {
+        QuadraticBezierSegment {
+            from: self.current,
+            ctrl,
+            to,
+        }
+        .for_each_flattened(self.tolerance, &mut |point| self.points.push(point));
+        self.current = to;
+        self.endpoint_id()
+    }
+
+    fn cubic_bezier_to(
+        &mut self,
+        ctrl1: tess::math::Point,
+        ctrl2: tess::math::Point,
+        to: tess::math::Point,
+    ) -> EndpointId {
+        CubicBezierSegment {
+            from: self.current,
+            ctrl1,
+            ctrl2,
+            to,
+        }
+        .for_each_flattened(self.tolerance, &mut |point| self.points.push(point));
+        self.current = to;
+        self.endpoint_id()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Dasher;
+    use crate::{
+        tess::{
+            self,
+            path::{traits::PathBuilder as _, Event},
+        },
+        DashPattern, LineSegmentBuilder, Transform,
+    };
+    use gee::{LineSegment, Point};
+    use std::{cell::Cell, rc::Rc};
+
+    /// Dashes the outline of a 10x10 square, returning each dash's points and whether it's
+    /// closed.
+    fn dash_square(pattern: DashPattern, close: bool) -> Vec<(Vec<(f32, f32)>, bool)> {
+        let mut builder = tess::path::Path::builder();
+        let mut dasher = Dasher::new(&mut builder, &pattern, 0.1, Rc::new(Cell::new(0.0)));
+        dasher.begin(tess::math::point(0.0, 0.0));
+        dasher.line_to(tess::math::point(10.0, 0.0));
+        dasher.line_to(tess::math::point(10.0, 10.0));
+        dasher.line_to(tess::math::point(0.0, 10.0));
+        if !close {
+            dasher.line_to(tess::math::point(0.0, 0.0));
+        }
+        dasher.end(close);
+        let round = |point: tess::math::Point| {
+            ((point.x * 1e3).round() / 1e3, (point.y * 1e3).round() / 1e3)
+        };
+        let mut dashes = Vec::new();
+        for event in &builder.build() {
+            match event {
+                Event::Begin { at } => dashes.push((vec![round(at)], false)),
+                Event::Line { to, .. } => dashes.last_mut().unwrap().0.push(round(to)),
+                Event::End { close, .. } => dashes.last_mut().unwrap().1 = close,
+                _ => unreachable!(),
+            }
+        }
+        dashes
+    }
+
+    #[test]
+    fn dash_across_start_of_closed_path() {
+        assert_eq!(
+            dash_square(DashPattern::dashed(12.0, 5.0), true),
+            [
+                (vec![(10.0, 7.0), (10.0, 10.0), (1.0, 10.0)], false),
+                (
+                    vec![(0.0, 6.0), (0.0, 0.0), (10.0, 0.0), (10.0, 2.0)],
+                    false
+                ),
+            ]
+        );
+        // dashes meeting exactly at the start are joined too
+        assert_eq!(dash_square(DashPattern::dashed(10.0, 5.0), true).len(), 2);
+        // unless the path isn't closed
+        assert_eq!(
+            dash_square(DashPattern::dashed(12.0, 5.0), false),
+            [
+                (vec![(0.0, 0.0), (10.0, 0.0), (10.0, 2.0)], false),
+                (vec![(10.0, 7.0), (10.0, 10.0), (1.0, 10.0)], false),
+                (vec![(0.0, 6.0), (0.0, 0.0)], false),
+            ]
+        );
+        // and only when the path starts on a dash
+        assert_eq!(
+            dash_square(DashPattern::dashed(12.0, 5.0).with_offset(14.0), true),
+            [
+                (vec![(3.0, 0.0), (10.0, 0.0), (10.0, 5.0)], false),
+                (vec![(10.0, 10.0), (0.0, 10.0), (0.0, 8.0)], false),
+                (vec![(0.0, 3.0), (0.0, 0.0)], false),
+            ]
+        );
+    }
+
+    #[test]
+    fn dash_around_closed_path() {
+        assert_eq!(
+            dash_square(DashPattern::dashed(50.0, 5.0), true),
+            [(
+                vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)],
+                true
+            )],
+        );
+    }
+
+    #[test]
+    fn dots_around_closed_path() {
+        let dots = dash_square(DashPattern::dotted(10.0), true);
+        assert_eq!(
+            dots.iter().map(|(points, _)| points[0]).collect::<Vec<_>>(),
+            [(10.0, 0.0), (10.0, 10.0), (0.0, 10.0), (0.0, 0.0)]
+        );
+    }
+
+    #[test]
+    fn dashes_after_transform() {
+        let poly = LineSegmentBuilder::new(LineSegment::new(
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+        ))
+        .with_stroke(1.0)
+        .with_dash_pattern(DashPattern::dashed(5.0, 5.0))
+        .with_transform(Transform::from_scale(2.0, 2.0))
+        .build();
+        let mut xs = poly
+            .vertices
+            .iter()
+            .map(|vertex| vertex.pos.x.round() as i32)
+            .collect::<Vec<_>>();
+        xs.sort_unstable();
+        xs.dedup();
+        assert_eq!(xs, [0, 5, 10, 15]);
+    }
+}
@@ -0,0 +1,102 @@
+use crate::{
+    options::{Options, StrokeOptions},
+    tess, validate, InvalidInput, PolyBuilder, DEFAULT_RADIUS,
+};
+use gee::{Angle, Circle, Point, Rect};
+
+#[derive(Clone, Debug)]
+pub struct EllipseBuilder {
+    center: Point,
+    radius_x: f32,
+    radius_y: f32,
+    rotation: Angle,
+    options: Options,
+}
+
+impl Default for EllipseBuilder {
+    fn default() -> Self {
+        Self {
+            center: Point::zero(),
+            radius_x: DEFAULT_RADIUS,
+            radius_y: DEFAULT_RADIUS,
+            rotation: Angle::from_radians(0.0),
+            options: Default::default(),
+        }
+    }
+}
+
+impl EllipseBuilder {
+    pub fn new(center: Point, radius_x: f32, radius_y: f32) -> Self {
+        Self::default()
+            .with_center(center)
+            .with_radii(radius_x, radius_y)
+    }
+
+    pub fn from_circle(circle: Circle) -> Self {
+        Self::new(circle.center(), circle.radius(), circle.radius())
+    }
+
+    pub fn with_center(mut self, center: Point) -> Self {
+        self.center = center;
+        self
+    }
+
+    pub fn with_radii(self, radius_x: f32, radius_y: f32) -> Self {
+        self.with_radius_x(radius_x).with_radius_y(radius_y)
+    }
+
+    pub fn with_radius_x(mut self, radius_x: f32) -> Self {
+        self.radius_x = radius_x;
+        self
+    }
+
+    pub fn with_radius_y(mut self, radius_y: f32) -> Self {
+        self.radius_y = radius_y;
+        self
+    }
+
+    /// Rotates the ellipse around its center.
+    pub fn with_rotation(mut self, rotation: impl Into<Angle>) -> Self {
+        self.rotation = rotation.into();
+        self
+    }
+
+    stroke!(public);
+
+    fill!();
+
+    build!();
+}
+
+impl PolyBuilder for EllipseBuilder {
+    fn options(&self) -> &Options {
+        &self.options
+    }
+
+    fn validate(&self) -> Result<(), InvalidInput> {
+        validate::point("center", self.center)?;
+        validate::non_negative("radius_x", self.radius_x)?;
+        validate::non_negative("radius_y", self.radius_y)?;
+        validate::angle("rotation", self.rotation)
+    }
+
+    fn bounding_rect(&self) -> Rect {
+        let (sin, cos) = self.rotation.radians().sin_cos();
+        let (rx, ry) = (self.radius_x, self.radius_y);
+        let half_width = (rx * rx * cos * cos + ry * ry * sin * sin).sqrt();
+        let half_height = (rx * rx * sin * sin + ry * ry * cos * cos).sqrt();
+        Rect::from_points(
+            Point::new(self.center.x - half_width, self.center.y - half_height),
+            Point::new(self.center.x + half_width, self.center.y + half_height),
+        )
+    }
+
+    fn build<B: tess::path::traits::PathBuilder>(self, builder: &mut B) {
+        builder.add_ellipse(
+            self.center.into(),
+            tess::math::vector(self.radius_x, self.radius_y),
+            tess::math::Angle::radians(self.rotation.radians()),
+            tess::path::Winding::Positive,
+        );
+    }
+}
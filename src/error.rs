@@ -0,0 +1,85 @@
+use crate::{tess, SvgPathError};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("`{builder}` failed to tessellate: {source}")]
+    Tessellation {
+        builder: &'static str,
+        source: TessellationFailure,
+    },
+    #[error("`{builder}` has invalid input: {source}")]
+    InvalidInput {
+        builder: &'static str,
+        source: InvalidInput,
+    },
+    #[error("`{builder}` doesn't support {source}")]
+    Unsupported {
+        builder: &'static str,
+        source: Unsupported,
+    },
+    #[error("Invalid SVG path data at byte {position}: {source}")]
+    SvgPath {
+        position: usize,
+        source: SvgPathError,
+    },
+    #[error("{vertex_count} vertices can't be indexed by `{index_type}`")]
+    IndexOverflow {
+        vertex_count: usize,
+        index_type: &'static str,
+    },
+}
+
+/// Why lyon couldn't tessellate a shape.
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum TessellationFailure {
+    #[error("a tessellation parameter isn't supported")]
+    UnsupportedParameter,
+    #[error("a vertex couldn't be constructed")]
+    InvalidVertex,
+    #[error("there are too many vertices")]
+    TooManyVertices,
+    /// A bug in lyon, which hopefully nobody ever sees.
+    #[error("lyon hit an internal error ({0})")]
+    Internal(String),
+}
+
+impl From<tess::TessellationError> for TessellationFailure {
+    fn from(err: tess::TessellationError) -> Self {
+        match err {
+            tess::TessellationError::UnsupportedParamater => Self::UnsupportedParameter,
+            tess::TessellationError::InvalidVertex => Self::InvalidVertex,
+            tess::TessellationError::TooManyVertices => Self::TooManyVertices,
+            tess::TessellationError::Internal(err) => Self::Internal(format!("{:?}", err)),
+        }
+    }
+}
+
+/// Builder input that can't be tessellated.
+#[derive(Clone, Debug, PartialEq, thiserror::Error)]
+pub enum InvalidInput {
+    #[error("regular polygons need at least {min} sides, but this one has {sides}")]
+    SideCount { sides: u32, min: u32 },
+    #[error("stars need at least {min} tips, but this one has {tips}")]
+    TipCount { tips: u32, min: u32 },
+    #[error("`{name}` must be finite, but it's {value}")]
+    NonFinite { name: &'static str, value: f32 },
+    #[error("`{name}` can't be negative, but it's {value}")]
+    Negative { name: &'static str, value: f32 },
+    #[error("`{name}` must be {expected}, but it's {value}")]
+    OutOfRange {
+        name: &'static str,
+        value: f32,
+        expected: &'static str,
+    },
+    #[error("can't fill a shape with zero-size bounds")]
+    ZeroSizeBounds,
+}
+
+/// Combinations of options a builder can't draw.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum Unsupported {
+    #[error("filling")]
+    Fill,
+    #[error("drawing without a fill or a stroke")]
+    NoFillOrStroke,
+}
@@ -0,0 +1,123 @@
+use crate::{
+    tess::{
+        self,
+        geom::{CubicBezierSegment, QuadraticBezierSegment},
+        path::{traits::Build, EndpointId},
+    },
+    PolyBuilder,
+};
+
+/// A closed polygon, without its first point repeated at the end.
+pub(crate) type Contour = Vec<tess::math::Point>;
+
+/// The area enclosed by a closed polygon, which is positive when it winds clockwise on screen,
+/// i.e. with y pointing down.
+pub(crate) fn signed_area(polygon: &[tess::math::Point]) -> f32 {
+    polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .map(|(a, b)| a.to_vector().cross(b.to_vector()))
+        .sum::<f32>()
+        / 2.0
+}
+
+/// Flattens the path `poly` would draw into closed polygons, applying its transform.
+///
+/// Open sub-paths are closed, just like filling would.
+pub(crate) fn flatten<T: PolyBuilder>(poly: T) -> Vec<Contour> {
+    let mut options = poly.options().clone();
+    options.resolve_tolerance(|| poly.bounding_rect());
+    let mut builder = tess::path::builder::Transformed::new(
+        Flattener {
+            tolerance: options.tolerance,
+            contours: Vec::new(),
+            current: Vec::new(),
+        },
+        options.transform.0,
+    );
+    poly.build(&mut builder);
+    builder.build()
+}
+
+struct Flattener {
+    tolerance: f32,
+    contours: Vec<Contour>,
+    current: Contour,
+}
+
+impl Flattener {
+    fn push(&mut self, point: tess::math::Point) -> EndpointId {
+        if self.current.last() != Some(&point) {
+            self.current.push(point);
+        }
+        EndpointId::INVALID
+    }
+
+    fn finish(&mut self) {
+        let mut contour = std::mem::take(&mut self.current);
+        if contour.len() > 1 && contour.first() == contour.last() {
+            contour.pop();
+        }
+        if contour.len() >= 3 {
+            self.contours.push(contour);
+        }
+    }
+}
+
+impl tess::path::traits::PathBuilder for Flattener {
+    fn begin(&mut self, at: tess::math::Point) -> EndpointId {
+        self.finish();
+        self.push(at)
+    }
+
+    fn end(&mut self, _close: bool) {
+        self.finish();
+    }
+
+    fn line_to(&mut self, to: tess::math::Point) -> EndpointId {
+        self.push(to)
+    }
+
+    fn quadratic_bezier_to(
+        &mut self,
+        ctrl: tess::math::Point,
+        to: tess::math::Point,
+    ) -> EndpointId {
+        let from = self.current.last().copied().unwrap_or(ctrl);
+        QuadraticBezierSegment { from, ctrl, to }.for_each_flattened(
+            self.tolerance,
+            &mut |point| {
+                self.push(point);
+            },
+        );
+        self.push(to)
+    }
+
+    fn cubic_bezier_to(
+        &mut self,
+        ctrl1: tess::math::Point,
+        ctrl2: tess::math::Point,
+        to: tess::math::Point,
+    ) -> EndpointId {
+        let from = self.current.last().copied().unwrap_or(ctrl1);
+        CubicBezierSegment {
+            from,
+            ctrl1,
+            ctrl2,
+            to,
+        }
+        .for_each_flattened(self.tolerance, &mut |point| {
+            self.push(point);
+        });
+        self.push(to)
+    }
+}
+
+impl Build for Flattener {
+    type PathType = Vec<Contour>;
+
+    fn build(mut self) -> Vec<Contour> {
+        self.finish();
+        self.contours
+    }
+}
@@ -1,13 +1,17 @@
 use crate::{
+    flatten::signed_area,
     options::{Options, StrokeOptions},
-    tess, PolyBuilder,
+    tess, validate, InvalidInput, PolyBuilder,
 };
 use gee::{LineSegment, Point, Rect};
 
 #[derive(Clone, Debug, Default)]
 pub struct FreePolyBuilder {
     points: Vec<tess::geom::Point<f32>>,
+    holes: Vec<Vec<tess::geom::Point<f32>>>,
     open: bool,
+    /// Cycled through for each point, so that e.g. stars can alternate between two radii.
+    corner_radii: Vec<f32>,
     bounding_rect: Option<Rect>,
     options: Options,
 }
@@ -71,6 +75,38 @@ impl FreePolyBuilder {
         self.with_points(lines.into_iter().flat_map(|line| line.points()))
     }
 
+    /// Cuts a hole out of the polygon.
+    ///
+    /// Holes are wound opposite to the outer contour, so they're cut out regardless of
+    /// `FillRule`. They don't contribute to the bounding rect used for texture coordinates.
+    pub fn with_hole(mut self, points: impl IntoIterator<Item = Point>) -> Self {
+        self.holes
+            .push(points.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub fn with_holes<I>(self, holes: impl IntoIterator<Item = I>) -> Self
+    where
+        I: IntoIterator<Item = Point>,
+    {
+        holes
+            .into_iter()
+            .fold(self, |this, hole| this.with_hole(hole))
+    }
+
+    /// Rounds every corner, including those of holes, with a tangent arc of `radius`.
+    ///
+    /// The arcs are shrunk where needed to fit within half of each adjacent edge. Values for
+    /// `radius` must be non-negative.
+    pub fn with_corner_radius(self, radius: f32) -> Self {
+        self.with_corner_radii(vec![radius])
+    }
+
+    pub(crate) fn with_corner_radii(mut self, corner_radii: Vec<f32>) -> Self {
+        self.corner_radii = corner_radii;
+        self
+    }
+
     pub fn with_stroke(mut self, stroke_width: f32, open: bool) -> Self {
         self.open = open;
         self._with_stroke(stroke_width)
@@ -109,14 +145,233 @@ impl PolyBuilder for FreePolyBuilder {
         &self.options
     }
 
+    fn validate(&self) -> Result<(), InvalidInput> {
+        self.points
+            .iter()
+            .chain(self.holes.iter().flatten())
+            .try_for_each(|&point| validate::point("points", point.into()))?;
+        self.corner_radii
+            .iter()
+            .try_for_each(|&radius| validate::non_negative("corner_radius", radius))
+    }
+
     fn bounding_rect(&self) -> Rect {
         self.bounding_rect.unwrap_or_default()
     }
 
     fn build<B: tess::path::traits::PathBuilder>(self, builder: &mut B) {
-        builder.add_polygon(tess::path::Polygon {
-            points: &self.points,
-            closed: !self.open,
-        });
+        add_polygon(builder, &self.points, !self.open, &self.corner_radii);
+        let outer_area = signed_area(&self.points);
+        for mut hole in self.holes {
+            let mut corner_radii = self
+                .corner_radii
+                .iter()
+                .copied()
+                .cycle()
+                .take(hole.len())
+                .collect::<Vec<_>>();
+            if signed_area(&hole).signum() == outer_area.signum() {
+                hole.reverse();
+                corner_radii.reverse();
+            }
+            add_polygon(builder, &hole, true, &corner_radii);
+        }
+    }
+}
+
+fn add_polygon<B: tess::path::traits::PathBuilder>(
+    builder: &mut B,
+    points: &[tess::geom::Point<f32>],
+    closed: bool,
+    corner_radii: &[f32],
+) {
+    if points.len() < 3 || corner_radii.iter().all(|&radius| radius == 0.0) {
+        builder.add_polygon(tess::path::Polygon { points, closed });
+        return;
+    }
+    let len = points.len();
+    let corner = |index: usize| {
+        if !closed && (index == 0 || index == len - 1) {
+            return Corner::sharp(points[index]);
+        }
+        Corner::new(
+            points[(index + len - 1) % len],
+            points[index],
+            points[(index + 1) % len],
+            corner_radii[index % corner_radii.len()],
+        )
+    };
+    let first = corner(0);
+    builder.begin(first.from);
+    first.build(builder);
+    for index in 1..len {
+        let corner = corner(index);
+        builder.line_to(corner.from);
+        corner.build(builder);
+    }
+    builder.end(closed);
+}
+
+/// A corner rounded by a tangent arc starting at `from`.
+struct Corner {
+    from: tess::geom::Point<f32>,
+    arc: Option<tess::geom::Arc<f32>>,
+}
+
+impl Corner {
+    fn sharp(at: tess::geom::Point<f32>) -> Self {
+        Self {
+            from: at,
+            arc: None,
+        }
+    }
+
+    fn new(
+        prev: tess::geom::Point<f32>,
+        at: tess::geom::Point<f32>,
+        next: tess::geom::Point<f32>,
+        radius: f32,
+    ) -> Self {
+        let (to_prev, to_next) = (prev - at, next - at);
+        let max_tangent = to_prev.length().min(to_next.length()) / 2.0;
+        let (to_prev, to_next) = (to_prev.normalize(), to_next.normalize());
+        // Half of the angle between the edges.
+        let half_angle = to_prev.dot(to_next).clamp(-1.0, 1.0).acos() / 2.0;
+        let tangent = (radius / half_angle.tan()).min(max_tangent);
+        let bisector = to_prev + to_next;
+        // Straight and nearly straight corners have nothing to round, and would otherwise put
+        // the center at infinity.
+        if tangent.is_nan() || tangent <= 0.0 || bisector.square_length() <= 1e-12 {
+            return Self::sharp(at);
+        }
+        let radius = tangent * half_angle.tan();
+        let center = at + bisector.normalize() * (tangent / half_angle.cos());
+        if !(radius > 0.0 && center.x.is_finite() && center.y.is_finite()) {
+            return Self::sharp(at);
+        }
+        let from = at + to_prev * tangent;
+        let turns_left = to_next.cross(to_prev) > 0.0;
+        let sweep = std::f32::consts::PI - 2.0 * half_angle;
+        Self {
+            from,
+            arc: Some(tess::geom::Arc {
+                center,
+                radii: tess::math::vector(radius, radius),
+                start_angle: (from - center).angle_from_x_axis(),
+                sweep_angle: tess::math::Angle::radians(if turns_left { sweep } else { -sweep }),
+                x_rotation: tess::math::Angle::zero(),
+            }),
+        }
+    }
+
+    fn build<B: tess::path::traits::PathBuilder>(&self, builder: &mut B) {
+        if let Some(arc) = self.arc {
+            arc.for_each_quadratic_bezier(&mut |curve| {
+                builder.quadratic_bezier_to(curve.ctrl, curve.to);
+            });
+        }
+    }
+}
+
+/// Counts the corners rounded by `corner_radius` options, i.e. the runs of curves.
+#[cfg(test)]
+pub(crate) fn rounded_corners<T: PolyBuilder>(poly: T) -> usize {
+    #[derive(Default)]
+    struct Counter {
+        corners: usize,
+        in_curve: bool,
+    }
+
+    impl tess::path::traits::PathBuilder for Counter {
+        fn begin(&mut self, _at: tess::math::Point) -> tess::path::EndpointId {
+            self.in_curve = false;
+            tess::path::EndpointId::INVALID
+        }
+
+        fn end(&mut self, _close: bool) {}
+
+        fn line_to(&mut self, _to: tess::math::Point) -> tess::path::EndpointId {
+            self.in_curve = false;
+            tess::path::EndpointId::INVALID
+        }
+
+        fn quadratic_bezier_to(
+            &mut self,
+            _ctrl: tess::math::Point,
+            _to: tess::math::Point,
+        ) -> tess::path::EndpointId {
+            if !self.in_curve {
+                self.corners += 1;
+                self.in_curve = true;
+            }
+            tess::path::EndpointId::INVALID
+        }
+
+        fn cubic_bezier_to(
+            &mut self,
+            ctrl1: tess::math::Point,
+            _ctrl2: tess::math::Point,
+            to: tess::math::Point,
+        ) -> tess::path::EndpointId {
+            self.quadratic_bezier_to(ctrl1, to)
+        }
+    }
+
+    let mut counter = Counter::default();
+    poly.build(&mut counter);
+    counter.corners
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square() -> Vec<Point> {
+        vec![
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(10.0, 10.0),
+            Point::new(0.0, 10.0),
+        ]
+    }
+
+    #[test]
+    fn rounds_every_corner() {
+        let poly = FreePolyBuilder::from_points(square()).with_corner_radius(2.0);
+        assert_eq!(rounded_corners(poly.clone()), 4);
+        assert_eq!(rounded_corners(poly.with_hole(square())), 8);
+    }
+
+    #[test]
+    fn leaves_ends_of_open_strokes_sharp() {
+        let poly = FreePolyBuilder::from_points(square())
+            .with_corner_radius(2.0)
+            .with_stroke_open(1.0);
+        assert_eq!(rounded_corners(poly), 2);
+    }
+
+    #[test]
+    fn collinear_points_stay_sharp() {
+        let poly = FreePolyBuilder::from_points([
+            Point::new(0.0, 0.0),
+            Point::new(5.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(10.0, 10.0),
+            Point::new(0.0, 10.0),
+        ])
+        .with_corner_radius(2.0);
+        assert_eq!(rounded_corners(poly.clone()), 4);
+        let corner = Corner::new(
+            tess::math::point(0.0, 0.0),
+            tess::math::point(5.0, 0.0),
+            tess::math::point(10.0, 0.0),
+            2.0,
+        );
+        assert!(corner.arc.is_none());
+        let poly = poly.try_build().unwrap();
+        assert!(poly
+            .vertices
+            .iter()
+            .all(|vertex| vertex.pos.x.is_finite() && vertex.pos.y.is_finite()));
     }
 }
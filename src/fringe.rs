@@ -0,0 +1,182 @@
+use crate::tess;
+use gee::Point;
+use std::collections::BTreeMap;
+
+/// Vertices recorded during tessellation, which a fringe can be extruded from.
+pub(crate) trait FringeSource {
+    type Vertex;
+
+    /// The position of the `nth` vertex of this tessellation pass.
+    fn position(&self, nth: usize) -> Point;
+
+    /// Records and constructs a copy of the `nth` vertex moved to `pos`, with zero coverage.
+    fn fringe_vertex(&mut self, nth: usize, pos: Point) -> Self::Vertex;
+}
+
+/// Extrudes a strip of triangles `width` wide from every edge on the boundary of the mesh.
+///
+/// Boundary edges are the ones that only belong to one triangle.
+pub(crate) fn add_fringe<S: FringeSource>(
+    source: &mut S,
+    buf: &mut tess::VertexBuffers<S::Vertex, u32>,
+    first_vertex: usize,
+    first_index: usize,
+    width: f32,
+) {
+    let position = |source: &S, index: u32| {
+        tess::math::Point::from(source.position(index as usize - first_vertex))
+    };
+
+    // (times seen, from, to, opposite vertex)
+    let mut edges = BTreeMap::<(u32, u32), (u32, u32, u32, u32)>::new();
+    for triangle in buf.indices[first_index..].chunks_exact(3) {
+        for (a, b, c) in [
+            (triangle[0], triangle[1], triangle[2]),
+            (triangle[1], triangle[2], triangle[0]),
+            (triangle[2], triangle[0], triangle[1]),
+        ] {
+            edges
+                .entry((a.min(b), a.max(b)))
+                .and_modify(|(count, ..)| *count += 1)
+                .or_insert((1, a, b, c));
+        }
+    }
+
+    // (sum of adjacent edge normals, any adjacent edge normal)
+    let mut normals = BTreeMap::new();
+    let mut boundary = Vec::new();
+    for (count, a, b, c) in edges.into_values() {
+        if count != 1 {
+            continue;
+        }
+        let (pa, pb, pc) = (
+            position(source, a),
+            position(source, b),
+            position(source, c),
+        );
+        let edge = pb - pa;
+        if edge.square_length() == 0.0 {
+            continue;
+        }
+        let mut normal = tess::math::vector(edge.y, -edge.x).normalize();
+        if normal.dot(pc - pa) > 0.0 {
+            normal = -normal;
+        }
+        for index in [a, b] {
+            normals
+                .entry(index)
+                .and_modify(|(sum, _)| *sum += normal)
+                .or_insert((normal, normal));
+        }
+        boundary.push((a, b));
+    }
+
+    let mut outer = BTreeMap::new();
+    for (index, (sum, adjacent)) in normals {
+        let direction = if sum.square_length() > f32::EPSILON {
+            sum.normalize()
+        } else {
+            adjacent
+        };
+        // miter the corners, within reason
+        let scale = direction.dot(adjacent).max(0.5).recip();
+        let pos = position(source, index) + direction * width * scale;
+        let vertex = source.fringe_vertex(index as usize - first_vertex, pos.into());
+        buf.vertices.push(vertex);
+        outer.insert(index, buf.vertices.len() as u32 - 1);
+    }
+
+    for (a, b) in boundary {
+        let (outer_a, outer_b) = (outer[&a], outer[&b]);
+        buf.indices
+            .extend_from_slice(&[a, b, outer_b, a, outer_b, outer_a]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FillVertex, FreePolyBuilder, LineSegmentBuilder, StrokeVertex, VertexConstructor};
+    use gee::{LineSegment, Point};
+
+    /// Keeps the position and coverage of each vertex.
+    struct Coverage;
+
+    impl VertexConstructor for Coverage {
+        type Vertex = (Point, f32);
+
+        fn new_fill_vertex(&mut self, vertex: FillVertex) -> Self::Vertex {
+            (vertex.pos, vertex.coverage)
+        }
+
+        fn new_stroke_vertex(&mut self, vertex: StrokeVertex) -> Self::Vertex {
+            (vertex.pos, vertex.coverage)
+        }
+    }
+
+    /// The rounded positions of the vertices with the given coverage.
+    fn positions(vertices: &[(Point, f32)], coverage: f32) -> Vec<(i32, i32)> {
+        let mut positions = vertices
+            .iter()
+            .filter(|(_, vertex_coverage)| *vertex_coverage == coverage)
+            .map(|(pos, _)| ((pos.x * 10.0).round() as i32, (pos.y * 10.0).round() as i32))
+            .collect::<Vec<_>>();
+        positions.sort_unstable();
+        positions
+    }
+
+    fn square(left: f32, top: f32, size: f32) -> Vec<Point> {
+        vec![
+            Point::new(left, top),
+            Point::new(left + size, top),
+            Point::new(left + size, top + size),
+            Point::new(left, top + size),
+        ]
+    }
+
+    #[test]
+    fn fill_fringe() {
+        let poly = FreePolyBuilder::from_points(square(0.0, 0.0, 10.0))
+            .with_hole(square(3.0, 3.0, 4.0))
+            .with_anti_aliasing(1.0)
+            .build_with(Coverage);
+        // each corner moves 1 away from both of its edges, outwards around the shape and inwards
+        // around the hole
+        assert_eq!(
+            positions(&poly.vertices, 0.0),
+            [
+                (-10, -10),
+                (-10, 110),
+                (40, 40),
+                (40, 60),
+                (60, 40),
+                (60, 60),
+                (110, -10),
+                (110, 110)
+            ]
+        );
+        assert_eq!(positions(&poly.vertices, 1.0).len(), 8);
+        // eight triangles for the fill, and two per boundary edge
+        assert_eq!(poly.indices.len(), 3 * 8 + 6 * 8);
+    }
+
+    #[test]
+    fn stroke_fringe() {
+        let poly = LineSegmentBuilder::new(LineSegment::new(
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+        ))
+        .with_stroke(2.0)
+        .with_anti_aliasing(0.5)
+        .build_with(Coverage);
+        assert_eq!(
+            positions(&poly.vertices, 0.0),
+            [(-5, -15), (-5, 15), (105, -15), (105, 15)]
+        );
+        assert_eq!(
+            positions(&poly.vertices, 1.0),
+            [(0, -10), (0, 10), (100, -10), (100, 10)]
+        );
+        // two triangles for the stroke, and two per boundary edge
+        assert_eq!(poly.indices.len(), 6 + 6 * 4);
+    }
+}
@@ -10,37 +10,182 @@ macro_rules! build {
         pub fn build(self) -> $crate::Poly {
             $crate::Poly::from_builder(self)
         }
+
+        /// Builds a `Poly` with `u16` indices, failing if there are too many vertices.
+        pub fn try_build_u16(self) -> Result<$crate::Poly<$crate::Vertex, u16>, $crate::Error> {
+            self.try_build()?.try_with_index_type()
+        }
+
+        /// Appends this shape to `batch`, returning its index in `Batch::items`.
+        pub fn try_add_to<C: $crate::VertexConstructor>(
+            self,
+            batch: &mut $crate::Batch<C>,
+        ) -> Result<usize, $crate::Error> {
+            batch.try_add(self)
+        }
+
+        pub fn add_to<C: $crate::VertexConstructor>(self, batch: &mut $crate::Batch<C>) -> usize {
+            batch.try_add(self).expect("failed to build `Poly`")
+        }
+
+        /// Builds into `poly`, reusing its allocations along with `tessellator`'s.
+        pub fn try_build_into<C: $crate::VertexConstructor>(
+            self,
+            tessellator: &mut $crate::Tessellator<C>,
+            poly: &mut $crate::Poly<C::Vertex>,
+        ) -> Result<(), $crate::Error> {
+            tessellator.try_build_into(self, poly)
+        }
+
+        pub fn build_into<C: $crate::VertexConstructor>(
+            self,
+            tessellator: &mut $crate::Tessellator<C>,
+            poly: &mut $crate::Poly<C::Vertex>,
+        ) {
+            tessellator
+                .try_build_into(self, poly)
+                .expect("failed to build `Poly`")
+        }
+
+        /// Builds a `Poly` with vertices produced by `constructor`.
+        pub fn try_build_with<C: $crate::VertexConstructor>(
+            self,
+            constructor: C,
+        ) -> Result<$crate::Poly<C::Vertex>, $crate::Error> {
+            $crate::Poly::try_from_builder_with(self, constructor)
+        }
+
+        pub fn build_with<C: $crate::VertexConstructor>(
+            self,
+            constructor: C,
+        ) -> $crate::Poly<C::Vertex> {
+            $crate::Poly::from_builder_with(self, constructor)
+        }
+
+        /// Converts this shape into a `PathBuilder`, e.g. for boolean operations.
+        ///
+        /// The options carry over, but any texture mapping specific to this builder doesn't.
+        /// Invalid input is reported here, just as `try_build` would report it.
+        pub fn to_path(self) -> Result<$crate::PathBuilder, $crate::Error> {
+            $crate::PathBuilder::try_from_builder(self)
+        }
+
+        /// Builds one level of detail per view scale, each with a maximum on-screen `error`.
+        ///
+        /// See `with_screen_tolerance` for what these mean.
+        pub fn try_build_lod(
+            self,
+            error: f32,
+            view_scales: impl IntoIterator<Item = f32>,
+        ) -> Result<$crate::Lod, $crate::Error> {
+            $crate::Lod::try_from_builder_with(
+                self,
+                $crate::DefaultVertexConstructor,
+                error,
+                view_scales,
+            )
+        }
+
+        pub fn build_lod(
+            self,
+            error: f32,
+            view_scales: impl IntoIterator<Item = f32>,
+        ) -> $crate::Lod {
+            self.try_build_lod(error, view_scales)
+                .expect("failed to build `Lod`")
+        }
+
+        /// Builds levels of detail with vertices produced by `constructor`.
+        pub fn try_build_lod_with<C: $crate::VertexConstructor>(
+            self,
+            constructor: C,
+            error: f32,
+            view_scales: impl IntoIterator<Item = f32>,
+        ) -> Result<$crate::Lod<C::Vertex>, $crate::Error> {
+            $crate::Lod::try_from_builder_with(self, constructor, error, view_scales)
+        }
+
+        pub fn build_lod_with<C: $crate::VertexConstructor>(
+            self,
+            constructor: C,
+            error: f32,
+            view_scales: impl IntoIterator<Item = f32>,
+        ) -> $crate::Lod<C::Vertex> {
+            self.try_build_lod_with(constructor, error, view_scales)
+                .expect("failed to build `Lod`")
+        }
     };
 }
 
+mod annulus;
+mod arc;
+mod batch;
 mod bezier;
+mod boolean;
 mod circle;
+mod dash;
+mod ellipse;
+mod error;
+mod flatten;
 mod free_poly;
+mod fringe;
 mod line_segment;
+mod lod;
+mod offset;
+mod paint;
+mod path;
 mod regular_poly;
+mod ring_sector;
 mod round_rect;
+mod sector;
 mod star;
+mod svg;
+mod svg_path;
+mod tessellator;
+mod transform;
+mod validate;
 mod vertex;
 
 pub use self::{
+    annulus::{AnnulusBuilder, TexCoordMapping},
+    arc::ArcBuilder,
+    batch::{Batch, BatchItem},
     bezier::{BezierBuilder, BezierSegment, ControlPoint},
     circle::CircleBuilder,
+    dash::DashPattern,
+    ellipse::EllipseBuilder,
+    error::{Error, InvalidInput, TessellationFailure, Unsupported},
     free_poly::FreePolyBuilder,
     line_segment::LineSegmentBuilder,
-    options::StrokeOptions,
+    lod::Lod,
+    options::{FillRule, LineCap, LineJoin, ScreenTolerance, StrokeOptions},
+    paint::{Color, GradientStop, Paint},
+    path::{PathBuilder, PathSegment},
     regular_poly::RegularPolyBuilder,
+    ring_sector::RingSectorBuilder,
     round_rect::RoundRectBuilder,
+    sector::SectorBuilder,
     star::StarBuilder,
-    vertex::Vertex,
+    svg::SvgOptions,
+    svg_path::SvgPathError,
+    tessellator::Tessellator,
+    transform::Transform,
+    vertex::{
+        ColoredVertex, ColoredVertexConstructor, DefaultVertexConstructor, FillVertex, Positioned,
+        Side, StrokeVertex, Vertex, VertexConstructor,
+    },
 };
 use self::{
+    dash::Dasher,
+    fringe::add_fringe,
     options::Options,
-    vertex::{FillVertexConstructor, StrokeVertexConstructor},
+    paint::Painter,
+    vertex::{FillTexCoords, FillVertexConstructor, StrokeVertexConstructor},
 };
 use gee::{Angle, Direction, Rect};
 use lyon_tessellation as tess;
+use std::{cell::Cell, ops::Range, rc::Rc};
 use tess::path::traits::Build as _;
-use thiserror::Error;
 
 pub const DEFAULT_RADIUS: f32 = 50.0;
 
@@ -48,68 +193,376 @@ pub fn default_start_angle() -> Angle {
     Direction::North.angle()
 }
 
-// TODO: lyon's error type doesn't impl `Display`/`Error`
-#[derive(Debug, Error)]
-#[error("Tesselation failed: {0:?}")]
-pub struct Error(tess::TessellationError);
-
-impl From<tess::TessellationError> for Error {
-    fn from(err: tess::TessellationError) -> Self {
-        Self(err)
-    }
+/// The name of a builder, for error messages.
+fn builder_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name)
 }
 
 /// Tesselated polygon vertices.
+///
+/// `V` is `Vertex` unless you build with your own `VertexConstructor`, and `I` is `u32` unless
+/// you convert with `try_with_index_type`.
 #[derive(Clone, Debug)]
-pub struct Poly {
-    pub vertices: Vec<Vertex>,
-    pub indices: Vec<u32>,
+pub struct Poly<V = Vertex, I = u32> {
+    pub vertices: Vec<V>,
+    pub indices: Vec<I>,
+    /// The range of `indices` making up the fill, if any.
+    pub fill_indices: Range<usize>,
+    /// The range of `indices` making up the stroke, if any.
+    ///
+    /// When filling and stroking at once, this always follows `fill_indices`.
+    pub stroke_indices: Range<usize>,
+}
+
+// derived `Default` would needlessly require `V: Default` and `I: Default`
+impl<V, I> Default for Poly<V, I> {
+    fn default() -> Self {
+        Self {
+            vertices: Vec::new(),
+            indices: Vec::new(),
+            fill_indices: 0..0,
+            stroke_indices: 0..0,
+        }
+    }
+}
+
+impl<V: Positioned, I> Poly<V, I> {
+    /// Transforms every vertex in place, e.g. to place a shape tessellated in unit space.
+    ///
+    /// Unlike `with_transform`, this also transforms stroke widths and fringes.
+    pub fn transform(&mut self, transform: Transform) {
+        for vertex in &mut self.vertices {
+            let pos = vertex.pos_mut();
+            *pos = transform.transform_point(*pos);
+        }
+    }
 }
 
 impl Poly {
     fn try_from_builder<T: PolyBuilder>(poly: T) -> Result<Self, Error> {
+        Self::try_from_builder_with(poly, DefaultVertexConstructor)
+    }
+
+    fn from_builder<T: PolyBuilder>(builder: T) -> Poly {
+        Self::try_from_builder(builder).expect("failed to build `Poly`")
+    }
+}
+
+impl<V> Poly<V> {
+    /// Converts `indices` to a smaller type, such as `u16`.
+    ///
+    /// Fails if any index doesn't fit, rather than truncating it.
+    pub fn try_with_index_type<I: TryFrom<u32>>(self) -> Result<Poly<V, I>, Error> {
+        let vertex_count = self.vertices.len();
+        let indices = self
+            .indices
+            .into_iter()
+            .map(I::try_from)
+            .collect::<Result<_, _>>()
+            .map_err(|_| Error::IndexOverflow {
+                vertex_count,
+                index_type: std::any::type_name::<I>(),
+            })?;
+        Ok(Poly {
+            vertices: self.vertices,
+            indices,
+            fill_indices: self.fill_indices,
+            stroke_indices: self.stroke_indices,
+        })
+    }
+
+    fn try_from_builder_with<T, C>(poly: T, constructor: C) -> Result<Self, Error>
+    where
+        T: PolyBuilder,
+        C: VertexConstructor<Vertex = V>,
+    {
         let mut buf = tess::VertexBuffers::new();
-        let options = poly.options();
-        let _count = match options
-            .stroke_options
-            .clone()
-            .map(StrokeVertexConstructor::new)
-        {
-            None => {
-                let mut tessellator = tess::FillTessellator::new();
-                let fill_options = options.fill_options();
-                let mut buf_builder = tess::BuffersBuilder::new(
-                    &mut buf,
-                    FillVertexConstructor::new(poly.bounding_rect()),
-                );
-                let mut builder = tessellator.builder(&fill_options, &mut buf_builder);
-                poly.build(&mut builder);
-                builder.build()?;
-            }
-            Some(vertex_constructor) => {
-                let mut tessellator = tess::StrokeTessellator::new();
-                let stroke_options = options.stroke_options();
-                let mut buf_builder = tess::BuffersBuilder::new(&mut buf, vertex_constructor);
-                let mut builder = tessellator.builder(&stroke_options, &mut buf_builder);
-                poly.build(&mut builder);
-                builder.build()?;
-            }
-        };
-        Ok(Self {
+        let item = Self::tessellate(
+            poly,
+            &mut Tessellator::with_constructor(constructor),
+            &mut buf,
+        )?;
+        Ok(Self::from_buffers(buf, item))
+    }
+
+    fn from_buffers(buf: tess::VertexBuffers<V, u32>, item: BatchItem) -> Self {
+        Self {
             vertices: buf.vertices,
             indices: buf.indices,
+            fill_indices: item.fill_indices,
+            stroke_indices: item.stroke_indices,
+        }
+    }
+
+    fn from_builder_with<T, C>(builder: T, constructor: C) -> Self
+    where
+        T: PolyBuilder,
+        C: VertexConstructor<Vertex = V>,
+    {
+        Self::try_from_builder_with(builder, constructor).expect("failed to build `Poly`")
+    }
+
+    /// Appends `poly` to `buf`, returning where its indices ended up.
+    fn tessellate<T, C>(
+        poly: T,
+        tessellator: &mut Tessellator<C>,
+        buf: &mut tess::VertexBuffers<V, u32>,
+    ) -> Result<BatchItem, Error>
+    where
+        T: PolyBuilder,
+        C: VertexConstructor<Vertex = V>,
+    {
+        let options = poly.options().clone();
+        Self::tessellate_with_options(poly, options, tessellator, buf)
+    }
+
+    /// Like `tessellate`, but with `options` in place of `poly`'s own.
+    fn tessellate_with_options<T, C>(
+        poly: T,
+        mut options: Options,
+        tessellator: &mut Tessellator<C>,
+        buf: &mut tess::VertexBuffers<V, u32>,
+    ) -> Result<BatchItem, Error>
+    where
+        T: PolyBuilder,
+        C: VertexConstructor<Vertex = V>,
+    {
+        let Tessellator {
+            fill,
+            stroke,
+            constructor,
+            fill_vertices,
+            stroke_vertices,
+        } = tessellator;
+        let builder = builder_name::<T>();
+        let invalid_input = |source| Error::InvalidInput { builder, source };
+        poly.validate().map_err(invalid_input)?;
+        options.validate().map_err(invalid_input)?;
+        if options.fill && !poly.fillable() {
+            return Err(Error::Unsupported {
+                builder,
+                source: Unsupported::Fill,
+            });
+        }
+        options.resolve_tolerance(|| poly.bounding_rect());
+        let start = buf.indices.len();
+        let mut fill_end = start;
+        match (options.fill, options.stroke_options.clone()) {
+            (true, None) => {
+                Self::fill(poly, &options, fill, constructor, fill_vertices, buf)?;
+                fill_end = buf.indices.len();
+            }
+            (true, Some(stroke_options)) => {
+                Self::fill(
+                    poly.clone(),
+                    &options,
+                    fill,
+                    constructor,
+                    fill_vertices,
+                    buf,
+                )?;
+                fill_end = buf.indices.len();
+                Self::stroke(
+                    poly,
+                    stroke_options,
+                    &options,
+                    stroke,
+                    constructor,
+                    stroke_vertices,
+                    buf,
+                )?;
+            }
+            (false, Some(stroke_options)) => {
+                Self::stroke(
+                    poly,
+                    stroke_options,
+                    &options,
+                    stroke,
+                    constructor,
+                    stroke_vertices,
+                    buf,
+                )?;
+            }
+            (false, None) => {
+                return Err(Error::Unsupported {
+                    builder,
+                    source: Unsupported::NoFillOrStroke,
+                })
+            }
+        }
+        Ok(BatchItem {
+            fill_indices: start..fill_end,
+            stroke_indices: fill_end..buf.indices.len(),
         })
     }
 
-    fn from_builder<T: PolyBuilder>(builder: T) -> Poly {
-        Self::try_from_builder(builder).expect("failed to build `Poly`")
+    fn fill<T, C>(
+        poly: T,
+        options: &Options,
+        tessellator: &mut tess::FillTessellator,
+        constructor: &mut C,
+        vertices: &mut Vec<FillVertex>,
+        buf: &mut tess::VertexBuffers<V, u32>,
+    ) -> Result<(), Error>
+    where
+        T: PolyBuilder,
+        C: VertexConstructor<Vertex = V>,
+    {
+        let bounding_rect = poly.bounding_rect();
+        if !(bounding_rect.size().width > 0.0 && bounding_rect.size().height > 0.0) {
+            return Err(Error::InvalidInput {
+                builder: builder_name::<T>(),
+                source: InvalidInput::ZeroSizeBounds,
+            });
+        }
+        let fill_options = options.fill_options();
+        let first_vertex = buf.vertices.len();
+        let first_index = buf.indices.len();
+        let painter = Painter::new(options.paint.clone(), || bounding_rect);
+        let mut vertex_constructor = FillVertexConstructor::new(
+            options.transform.inverse().unwrap_or_default(),
+            poly.fill_tex_coords(),
+            painter,
+            options.anti_aliasing.is_some(),
+            constructor,
+            vertices,
+        );
+        let mut buf_builder = tess::BuffersBuilder::new(buf, &mut vertex_constructor);
+        let mut builder = tess::path::builder::Transformed::new(
+            tessellator.builder(&fill_options, &mut buf_builder),
+            options.transform.0,
+        );
+        poly.build(&mut builder);
+        builder.build().map_err(|err| Error::Tessellation {
+            builder: builder_name::<T>(),
+            source: err.into(),
+        })?;
+        if let Some(fringe_width) = options.anti_aliasing {
+            add_fringe(
+                &mut vertex_constructor,
+                buf,
+                first_vertex,
+                first_index,
+                fringe_width,
+            );
+        }
+        vertex_constructor.fix_seam(buf, first_vertex, first_index);
+        Ok(())
+    }
+
+    fn stroke<T, C>(
+        poly: T,
+        stroke_options: StrokeOptions,
+        options: &Options,
+        tessellator: &mut tess::StrokeTessellator,
+        constructor: &mut C,
+        vertices: &mut Vec<StrokeVertex>,
+        buf: &mut tess::VertexBuffers<V, u32>,
+    ) -> Result<(), Error>
+    where
+        T: PolyBuilder,
+        C: VertexConstructor<Vertex = V>,
+    {
+        let lyon_stroke_options = options.stroke_options();
+        let first_vertex = buf.vertices.len();
+        let first_index = buf.indices.len();
+        let advancement_offset = Rc::new(Cell::new(0.0));
+        let dash_pattern = stroke_options.dash_pattern.clone();
+        let mut vertex_constructor = StrokeVertexConstructor::new(
+            options.transform.inverse().unwrap_or_default(),
+            stroke_options,
+            Painter::new(options.paint.clone(), || poly.bounding_rect()),
+            Rc::clone(&advancement_offset),
+            options.anti_aliasing.is_some(),
+            constructor,
+            vertices,
+        );
+        let mut buf_builder = tess::BuffersBuilder::new(buf, &mut vertex_constructor);
+        let mut builder = tessellator.builder(&lyon_stroke_options, &mut buf_builder);
+        // Dashing happens after the transform, like the rest of the stroke.
+        let result = match &dash_pattern {
+            Some(dash_pattern) => {
+                poly.build(&mut tess::path::builder::Transformed::new(
+                    Dasher::new(
+                        &mut builder,
+                        dash_pattern,
+                        options.tolerance,
+                        advancement_offset,
+                    ),
+                    options.transform.0,
+                ));
+                builder.build()
+            }
+            None => {
+                let mut builder =
+                    tess::path::builder::Transformed::new(builder, options.transform.0);
+                poly.build(&mut builder);
+                builder.build()
+            }
+        };
+        result.map_err(|err| Error::Tessellation {
+            builder: builder_name::<T>(),
+            source: err.into(),
+        })?;
+        if let Some(fringe_width) = options.anti_aliasing {
+            add_fringe(
+                &mut vertex_constructor,
+                buf,
+                first_vertex,
+                first_index,
+                fringe_width,
+            );
+        }
+        Ok(())
     }
 }
 
-trait PolyBuilder {
+trait PolyBuilder: Clone {
     fn options(&self) -> &Options;
 
+    /// Checks for input that would make tessellation panic or produce garbage.
+    fn validate(&self) -> Result<(), InvalidInput>;
+
+    fn fillable(&self) -> bool {
+        true
+    }
+
     fn bounding_rect(&self) -> Rect;
 
+    fn fill_tex_coords(&self) -> FillTexCoords {
+        FillTexCoords::new(self.bounding_rect())
+    }
+
     fn build<B: tess::path::traits::PathBuilder>(self, builder: &mut B);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gee::Point;
+
+    #[test]
+    fn u16_index_overflow() {
+        let points = |count: usize| {
+            (0..count).map(move |nth| {
+                let angle = nth as f32 / count as f32 * std::f32::consts::TAU;
+                Point::new(angle.cos() * 1000.0, angle.sin() * 1000.0)
+            })
+        };
+        let small = FreePolyBuilder::from_points(points(100)).build();
+        let small_u16 = small.clone().try_with_index_type::<u16>().unwrap();
+        assert!(small
+            .indices
+            .iter()
+            .zip(&small_u16.indices)
+            .all(|(&index, &index_u16)| index == u32::from(index_u16)));
+
+        let large = FreePolyBuilder::from_points(points(70_000));
+        assert!(large.clone().build().vertices.len() > usize::from(u16::MAX) + 1);
+        assert!(matches!(
+            large.try_build_u16(),
+            Err(Error::IndexOverflow { vertex_count, .. }) if vertex_count > 65536
+        ));
+    }
+}
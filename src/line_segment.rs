@@ -1,6 +1,6 @@
 use crate::{
     options::{Options, StrokeOptions},
-    tess, PolyBuilder, DEFAULT_RADIUS,
+    tess, validate, InvalidInput, PolyBuilder, DEFAULT_RADIUS,
 };
 use gee::{LineSegment, Point, Rect};
 
@@ -14,7 +14,7 @@ impl Default for LineSegmentBuilder {
     fn default() -> Self {
         Self {
             line: LineSegment::new(Point::new(0.0, 0.0), Point::new(DEFAULT_RADIUS * 2.0, 0.0)),
-            options: Options::default().with_stroke(Default::default()),
+            options: Options::default().with_stroke_opts(StrokeOptions::default()),
         }
     }
 }
@@ -39,8 +39,17 @@ impl PolyBuilder for LineSegmentBuilder {
         &self.options
     }
 
+    fn validate(&self) -> Result<(), InvalidInput> {
+        validate::point("from", self.line.from)?;
+        validate::point("to", self.line.to)
+    }
+
+    fn fillable(&self) -> bool {
+        false
+    }
+
     fn bounding_rect(&self) -> Rect {
-        todo!("line segments can't be filled")
+        Rect::from_points(self.line.from, self.line.to)
     }
 
     fn build<B: tess::path::traits::PathBuilder>(self, builder: &mut B) {
@@ -0,0 +1,104 @@
+use crate::{
+    options::ScreenTolerance, tess, Error, Poly, PolyBuilder, Tessellator, Vertex,
+    VertexConstructor,
+};
+
+/// The same shape tessellated for several view scales, so zooming can switch between them.
+#[derive(Clone, Debug)]
+pub struct Lod<V = Vertex> {
+    // sorted by view scale, so coarser levels come first
+    levels: Vec<(f32, Poly<V>)>,
+}
+
+impl<V> Lod<V> {
+    pub(crate) fn try_from_builder_with<T, C>(
+        poly: T,
+        constructor: C,
+        error: f32,
+        view_scales: impl IntoIterator<Item = f32>,
+    ) -> Result<Self, Error>
+    where
+        T: PolyBuilder,
+        C: VertexConstructor<Vertex = V>,
+    {
+        let mut view_scales = view_scales.into_iter().collect::<Vec<_>>();
+        if view_scales.is_empty() {
+            view_scales.push(1.0);
+        }
+        view_scales.sort_by(f32::total_cmp);
+        view_scales.dedup();
+        let mut tessellator = Tessellator::with_constructor(constructor);
+        let levels = view_scales
+            .into_iter()
+            .map(|view_scale| {
+                let mut options = poly.options().clone();
+                options.screen_tolerance = Some(ScreenTolerance { error, view_scale });
+                let mut buf = tess::VertexBuffers::new();
+                let item = Poly::tessellate_with_options(
+                    poly.clone(),
+                    options,
+                    &mut tessellator,
+                    &mut buf,
+                )?;
+                Ok((view_scale, Poly::from_buffers(buf, item)))
+            })
+            .collect::<Result<_, Error>>()?;
+        Ok(Self { levels })
+    }
+
+    /// The levels and the view scales they were built for, from coarsest to finest.
+    ///
+    /// There's always at least one; if no view scales were given, a view scale of 1 is used.
+    pub fn levels(&self) -> impl Iterator<Item = (f32, &Poly<V>)> {
+        self.levels
+            .iter()
+            .map(|(view_scale, poly)| (*view_scale, poly))
+    }
+
+    /// The coarsest level that's still within the error at `view_scale`.
+    ///
+    /// Past the largest view scale, this is the finest level.
+    pub fn select(&self, view_scale: f32) -> &Poly<V> {
+        let (_, poly) = self
+            .levels
+            .iter()
+            .find(|(level_scale, _)| *level_scale >= view_scale)
+            .unwrap_or_else(|| self.levels.last().expect("`Lod` is never empty"));
+        poly
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::CircleBuilder;
+    use gee::Circle;
+
+    #[test]
+    fn select() {
+        let lod = CircleBuilder::new(Circle::from_radius(100.0)).build_lod(0.5, [4.0, 1.0, 2.0]);
+        let levels = lod.levels().collect::<Vec<_>>();
+        assert_eq!(
+            levels.iter().map(|(scale, _)| *scale).collect::<Vec<_>>(),
+            [1.0, 2.0, 4.0]
+        );
+        // finer levels need more vertices
+        assert!(levels
+            .windows(2)
+            .all(|pair| pair[0].1.vertices.len() < pair[1].1.vertices.len()));
+
+        let selected = |view_scale: f32| {
+            levels
+                .iter()
+                .position(|(_, poly)| std::ptr::eq(*poly, lod.select(view_scale)))
+                .unwrap()
+        };
+        assert_eq!(selected(0.0), 0);
+        assert_eq!(selected(0.5), 0);
+        assert_eq!(selected(1.0), 0);
+        assert_eq!(selected(1.5), 1);
+        assert_eq!(selected(2.0), 1);
+        assert_eq!(selected(3.0), 2);
+        assert_eq!(selected(4.0), 2);
+        assert_eq!(selected(100.0), 2);
+    }
+}
@@ -0,0 +1,301 @@
+use crate::{
+    boolean::{combine, BooleanOp, Shape},
+    flatten::{signed_area, Contour},
+    tess, validate, Error, FillRule, LineJoin, PathBuilder, PolyBuilder as _, Transform,
+};
+
+type Point = tess::math::Point;
+type Vector = tess::math::Vector;
+
+const MITER_LIMIT: f32 = tess::StrokeOptions::DEFAULT_MITER_LIMIT;
+
+/// Offsetting shapes.
+///
+/// The shape is flattened and then grown or shrunk by sweeping a pen of the given `distance`
+/// along its outline, which is measured in output space, i.e. after the transform. Like boolean
+/// operations, the result is made of straight lines and keeps the options of `self`, apart from
+/// having no transform and using `FillRule::NonZero`. Any shape can be offset by converting it
+/// with `to_path`.
+impl PathBuilder {
+    /// Grows the shape by `distance`, using `join` where the offset outline turns a corner.
+    ///
+    /// Negative distances shrink the shape instead. `LineJoin::Miter` falls back to
+    /// `LineJoin::Bevel` past the default miter limit. A non-finite `distance` is an error.
+    pub fn offset(self, distance: f32, join: LineJoin) -> Result<Self, Error> {
+        validate::finite("distance", distance).map_err(|source| Error::InvalidInput {
+            builder: crate::builder_name::<Self>(),
+            source,
+        })?;
+        let mut options = self.options().clone();
+        options.resolve_tolerance(|| self.bounding_rect());
+        let tolerance = options.tolerance;
+        let shape = Shape::new(self);
+        let pen = Shape {
+            contours: pen_contours(&shape.contours, distance.abs(), join, tolerance),
+            fill_rule: FillRule::NonZero,
+        };
+        let op = if distance < 0.0 {
+            BooleanOp::Difference
+        } else {
+            BooleanOp::Union
+        };
+        options.transform = Transform::identity();
+        options.fill_rule = FillRule::NonZero;
+        Ok(PathBuilder::from_polygons(
+            combine(&shape, &pen, op),
+            options,
+        ))
+    }
+
+    /// Shrinks the shape by `distance`, using `join` where the offset outline turns a corner.
+    ///
+    /// This is the same as `offset` with the distance negated.
+    pub fn inset(self, distance: f32, join: LineJoin) -> Result<Self, Error> {
+        self.offset(-distance, join)
+    }
+}
+
+/// The area swept by a pen of radius `distance` moving along each contour, as a set of
+/// polygons that all wind the same way.
+fn pen_contours(
+    contours: &[Contour],
+    distance: f32,
+    join: LineJoin,
+    tolerance: f32,
+) -> Vec<Contour> {
+    let mut pen = Vec::new();
+    if distance == 0.0 {
+        return pen;
+    }
+    for contour in contours {
+        let directions = contour
+            .iter()
+            .zip(contour.iter().cycle().skip(1))
+            .map(|(&from, &to)| (to - from).normalize())
+            .collect::<Vec<_>>();
+        for (index, &from) in contour.iter().enumerate() {
+            let to = contour[(index + 1) % contour.len()];
+            let normal = left_normal(directions[index]) * distance;
+            pen.push(vec![from - normal, to - normal, to + normal, from + normal]);
+
+            let incoming = directions[(index + directions.len() - 1) % directions.len()];
+            let outgoing = directions[index];
+            let turn = incoming.cross(outgoing);
+            let straight = incoming.dot(outgoing) > 0.0;
+            // The offset outline only leaves a gap on the outside of the turn, or on both sides
+            // when the contour doubles back on itself.
+            if turn.abs() > 1e-6 || !straight {
+                if turn <= 0.0 || !straight {
+                    pen.push(corner(
+                        from, incoming, outgoing, 1.0, distance, join, tolerance,
+                    ));
+                }
+                if turn >= 0.0 || !straight {
+                    pen.push(corner(
+                        from, incoming, outgoing, -1.0, distance, join, tolerance,
+                    ));
+                }
+            }
+        }
+    }
+    for polygon in &mut pen {
+        if signed_area(polygon) < 0.0 {
+            polygon.reverse();
+        }
+    }
+    pen
+}
+
+/// The wedge filling the gap at a corner, on the left side if `side` is positive and on the
+/// right side otherwise.
+fn corner(
+    at: Point,
+    incoming: Vector,
+    outgoing: Vector,
+    side: f32,
+    distance: f32,
+    join: LineJoin,
+    tolerance: f32,
+) -> Contour {
+    let start = left_normal(incoming) * side;
+    let end = left_normal(outgoing) * side;
+    let sum = start + end;
+    // The ratio of the miter's length to the distance.
+    let miter_ratio = 2.0 / sum.length();
+    let mut polygon = vec![at, at + start * distance];
+    match join {
+        LineJoin::Round => {
+            let sweep = start.cross(end).atan2(start.dot(end));
+            let step = 2.0 * (1.0 - (tolerance / distance).min(1.0)).acos();
+            let steps = (sweep.abs() / step.max(1e-3)).ceil().min(1024.0) as u32;
+            let start_angle = start.y.atan2(start.x);
+            polygon.extend((1..steps).map(|nth| {
+                let angle = start_angle + sweep * nth as f32 / steps as f32;
+                at + tess::math::vector(angle.cos(), angle.sin()) * distance
+            }));
+        }
+        LineJoin::Miter | LineJoin::MiterClip if miter_ratio <= MITER_LIMIT => {
+            polygon.push(at + sum * (2.0 * distance / sum.square_length()));
+        }
+        LineJoin::MiterClip => {
+            let bisector = if sum.square_length() > 1e-12 {
+                sum.normalize()
+            } else {
+                incoming
+            };
+            let clip = MITER_LIMIT * distance;
+            let along = |from: Vector, direction: Vector| {
+                let from = from * distance;
+                at + from + direction * ((clip - from.dot(bisector)) / direction.dot(bisector))
+            };
+            polygon.push(along(start, incoming));
+            polygon.push(along(end, -outgoing));
+        }
+        LineJoin::Miter | LineJoin::Bevel => {}
+    }
+    polygon.push(at + end * distance);
+    polygon
+}
+
+fn left_normal(direction: Vector) -> Vector {
+    tess::math::vector(-direction.y, direction.x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{flatten::flatten, InvalidInput, StarBuilder};
+
+    fn square(size: f32) -> PathBuilder {
+        PathBuilder::new()
+            .move_to(gee::Point::new(0.0, 0.0))
+            .line_to(gee::Point::new(size, 0.0))
+            .line_to(gee::Point::new(size, size))
+            .line_to(gee::Point::new(0.0, size))
+            .close()
+    }
+
+    fn star() -> PathBuilder {
+        StarBuilder::pentagram()
+            .with_center_and_radius(gee::Point::zero(), 20.0)
+            .with_inner_radius_ratio(0.5)
+            .to_path()
+            .unwrap()
+    }
+
+    fn area(path: PathBuilder) -> f32 {
+        flatten(path)
+            .iter()
+            .map(|contour| signed_area(contour))
+            .sum()
+    }
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() <= expected.abs() * 1e-3,
+            "{actual} != {expected}"
+        );
+    }
+
+    #[test]
+    fn square_inset() {
+        for join in [
+            LineJoin::Miter,
+            LineJoin::MiterClip,
+            LineJoin::Round,
+            LineJoin::Bevel,
+        ] {
+            assert_close(area(square(20.0).inset(5.0, join).unwrap()), 100.0);
+        }
+        assert!(flatten(square(20.0).inset(10.0, LineJoin::Miter).unwrap()).is_empty());
+    }
+
+    #[test]
+    fn square_offset() {
+        assert_close(
+            area(square(20.0).offset(5.0, LineJoin::Miter).unwrap()),
+            900.0,
+        );
+        assert_close(
+            area(square(20.0).offset(5.0, LineJoin::MiterClip).unwrap()),
+            900.0,
+        );
+        assert_close(
+            area(square(20.0).offset(5.0, LineJoin::Bevel).unwrap()),
+            850.0,
+        );
+        // The flattened arcs sit just inside the circle.
+        let round = area(square(20.0).offset(5.0, LineJoin::Round).unwrap());
+        let exact = 800.0 + 25.0 * std::f32::consts::PI;
+        assert!(round <= exact && round > exact * 0.99, "{round} != {exact}");
+        assert_close(
+            area(square(20.0).offset(0.0, LineJoin::Round).unwrap()),
+            400.0,
+        );
+    }
+
+    #[test]
+    fn star_offset() {
+        let original = area(star());
+        // Every edge of a regular star is the same distance from its center, so a mitered
+        // offset is the star scaled about its center.
+        let contour = &flatten(star())[0];
+        let edge = (contour[1] - contour[0]).normalize();
+        let apothem = edge.cross(contour[0].to_vector()).abs();
+        let scaled = |distance: f32| original * ((apothem + distance) / apothem).powi(2);
+
+        let miter = area(star().offset(2.0, LineJoin::Miter).unwrap());
+        let round = area(star().offset(2.0, LineJoin::Round).unwrap());
+        let bevel = area(star().offset(2.0, LineJoin::Bevel).unwrap());
+        assert_close(miter, scaled(2.0));
+        assert_close(
+            area(star().offset(2.0, LineJoin::MiterClip).unwrap()),
+            scaled(2.0),
+        );
+        assert!(original < bevel && bevel < round && round < miter);
+
+        let miter = area(star().inset(2.0, LineJoin::Miter).unwrap());
+        let round = area(star().inset(2.0, LineJoin::Round).unwrap());
+        let bevel = area(star().inset(2.0, LineJoin::Bevel).unwrap());
+        assert_close(miter, scaled(-2.0));
+        assert!(miter < round && round < bevel && bevel < original);
+    }
+
+    #[test]
+    fn miter_limit() {
+        // The tip is past the miter limit, so it gets beveled or clipped, while the other two
+        // corners are mitered.
+        let spike = || {
+            PathBuilder::new()
+                .move_to(gee::Point::new(0.0, 0.0))
+                .line_to(gee::Point::new(100.0, 5.0))
+                .line_to(gee::Point::new(0.0, 10.0))
+                .close()
+        };
+        let miter = area(spike().offset(1.0, LineJoin::Miter).unwrap());
+        let clip = area(spike().offset(1.0, LineJoin::MiterClip).unwrap());
+        let bevel = area(spike().offset(1.0, LineJoin::Bevel).unwrap());
+        assert!(bevel < miter && miter < clip, "{bevel} {miter} {clip}");
+        // A full miter at the tip would reach about 20 times the distance past it.
+        assert!(clip - bevel < 10.0);
+    }
+
+    #[test]
+    fn non_finite_distance() {
+        for distance in [f32::NAN, f32::INFINITY] {
+            assert!(matches!(
+                square(20.0).offset(distance, LineJoin::Round),
+                Err(Error::InvalidInput {
+                    builder: "PathBuilder",
+                    source: InvalidInput::NonFinite {
+                        name: "distance",
+                        ..
+                    },
+                })
+            ));
+        }
+        assert!(square(20.0)
+            .inset(f32::NEG_INFINITY, LineJoin::Round)
+            .is_err());
+    }
+}
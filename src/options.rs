@@ -1,10 +1,82 @@
-use crate::tess;
+use crate::{tess, validate, DashPattern, InvalidInput, Paint, Transform};
+use gee::Rect;
+
+/// The shape drawn at the ends of an open stroke.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum LineCap {
+    /// The stroke ends exactly at the end of the path.
+    #[default]
+    Butt,
+    /// The stroke extends past the end of the path by half the stroke width.
+    Square,
+    /// The stroke ends with a half circle.
+    Round,
+}
+
+impl From<LineCap> for tess::LineCap {
+    fn from(cap: LineCap) -> Self {
+        match cap {
+            LineCap::Butt => Self::Butt,
+            LineCap::Square => Self::Square,
+            LineCap::Round => Self::Round,
+        }
+    }
+}
+
+/// The shape drawn where two segments of a stroke meet.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum LineJoin {
+    /// Sharp corners, falling back to `Bevel` past the miter limit.
+    #[default]
+    Miter,
+    /// Sharp corners, clipped at the miter limit.
+    MiterClip,
+    /// Rounded corners.
+    Round,
+    /// Flattened corners.
+    Bevel,
+}
+
+impl From<LineJoin> for tess::LineJoin {
+    fn from(join: LineJoin) -> Self {
+        match join {
+            LineJoin::Miter => Self::Miter,
+            LineJoin::MiterClip => Self::MiterClip,
+            LineJoin::Round => Self::Round,
+            LineJoin::Bevel => Self::Bevel,
+        }
+    }
+}
+
+/// How to decide which regions of a path are inside of it when filling.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum FillRule {
+    /// Regions enclosed an odd number of times are filled.
+    #[default]
+    EvenOdd,
+    /// Regions with a winding number other than zero are filled.
+    NonZero,
+}
+
+impl From<FillRule> for tess::FillRule {
+    fn from(rule: FillRule) -> Self {
+        match rule {
+            FillRule::EvenOdd => Self::EvenOdd,
+            FillRule::NonZero => Self::NonZero,
+        }
+    }
+}
 
 #[derive(Clone, Debug)]
 #[non_exhaustive]
 pub struct StrokeOptions {
     pub texture_aspect_ratio: f32,
     pub stroke_width: f32,
+    pub start_cap: LineCap,
+    pub end_cap: LineCap,
+    pub line_join: LineJoin,
+    pub miter_limit: f32,
+    pub dash_pattern: Option<DashPattern>,
 }
 
 impl Default for StrokeOptions {
@@ -12,6 +84,11 @@ impl Default for StrokeOptions {
         Self {
             texture_aspect_ratio: 1.0,
             stroke_width: 1.0,
+            start_cap: Default::default(),
+            end_cap: Default::default(),
+            line_join: Default::default(),
+            miter_limit: tess::StrokeOptions::DEFAULT_MITER_LIMIT,
+            dash_pattern: None,
         }
     }
 }
@@ -33,20 +110,129 @@ impl StrokeOptions {
         self.texture_aspect_ratio = texture_aspect_ratio;
         self
     }
+
+    /// Sets both the start and end caps.
+    pub fn with_line_cap(self, cap: LineCap) -> Self {
+        self.with_start_cap(cap).with_end_cap(cap)
+    }
+
+    pub fn with_start_cap(mut self, start_cap: LineCap) -> Self {
+        self.start_cap = start_cap;
+        self
+    }
+
+    pub fn with_end_cap(mut self, end_cap: LineCap) -> Self {
+        self.end_cap = end_cap;
+        self
+    }
+
+    pub fn with_line_join(mut self, line_join: LineJoin) -> Self {
+        self.line_join = line_join;
+        self
+    }
+
+    /// The limit on the ratio of a miter's length to the stroke width.
+    ///
+    /// Values for `miter_limit` must be at least 1.
+    pub fn with_miter_limit(mut self, miter_limit: f32) -> Self {
+        self.miter_limit = miter_limit;
+        self
+    }
+
+    /// Splits the stroke into dashes.
+    ///
+    /// The `y` texture coordinate keeps advancing across gaps, so dash textures tile seamlessly.
+    pub fn with_dash_pattern(mut self, dash_pattern: DashPattern) -> Self {
+        self.dash_pattern = Some(dash_pattern);
+        self
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), InvalidInput> {
+        validate::positive("stroke_width", self.stroke_width)?;
+        validate::finite("texture_aspect_ratio", self.texture_aspect_ratio)?;
+        validate::finite("miter_limit", self.miter_limit)?;
+        if self.miter_limit < tess::StrokeOptions::MINIMUM_MITER_LIMIT {
+            return Err(InvalidInput::OutOfRange {
+                name: "miter_limit",
+                value: self.miter_limit,
+                expected: "at least 1",
+            });
+        }
+        if let Some(dash_pattern) = &self.dash_pattern {
+            dash_pattern.validate()?;
+        }
+        Ok(())
+    }
+}
+
+/// A tolerance measured on screen, rather than in path units.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScreenTolerance {
+    /// The maximum distance from the true curve on screen, e.g. in pixels.
+    pub error: f32,
+    /// How many screen units one unit of the transformed path covers.
+    pub view_scale: f32,
+}
+
+// even tiny shapes shouldn't get much coarser than this, relative to their size
+const MAX_RELATIVE_TOLERANCE: f32 = 0.05;
+
+/// Stroke styling set directly on `Options`, which applies to whichever stroke is set, before or
+/// after.
+#[derive(Clone, Debug, Default)]
+struct StrokeStyle {
+    start_cap: Option<LineCap>,
+    end_cap: Option<LineCap>,
+    line_join: Option<LineJoin>,
+    miter_limit: Option<f32>,
+    dash_pattern: Option<DashPattern>,
+}
+
+impl StrokeStyle {
+    fn apply(&self, mut stroke_options: StrokeOptions) -> StrokeOptions {
+        let Self {
+            start_cap,
+            end_cap,
+            line_join,
+            miter_limit,
+            dash_pattern,
+        } = self.clone();
+        stroke_options.start_cap = start_cap.unwrap_or(stroke_options.start_cap);
+        stroke_options.end_cap = end_cap.unwrap_or(stroke_options.end_cap);
+        stroke_options.line_join = line_join.unwrap_or(stroke_options.line_join);
+        stroke_options.miter_limit = miter_limit.unwrap_or(stroke_options.miter_limit);
+        stroke_options.dash_pattern = dash_pattern.or(stroke_options.dash_pattern);
+        stroke_options
+    }
 }
 
 #[derive(Clone, Debug)]
 #[non_exhaustive]
 pub struct Options {
+    pub fill: bool,
+    pub fill_rule: FillRule,
     pub stroke_options: Option<StrokeOptions>,
+    pub paint: Option<Paint>,
+    pub anti_aliasing: Option<f32>,
+    pub transform: Transform,
     pub tolerance: f32,
+    /// Overrides `tolerance` when set.
+    pub screen_tolerance: Option<ScreenTolerance>,
+    stroke_style: StrokeStyle,
 }
 
 impl Default for Options {
     fn default() -> Self {
         Self {
+            fill: true,
+            fill_rule: Default::default(),
             stroke_options: None,
+            paint: None,
+            anti_aliasing: None,
+            transform: Transform::identity(),
             tolerance: tess::FillOptions::DEFAULT_TOLERANCE,
+            screen_tolerance: None,
+            stroke_style: Default::default(),
         }
     }
 }
@@ -57,54 +243,198 @@ impl Options {
     }
 
     pub fn with_fill(mut self) -> Self {
+        self.fill = true;
         self.stroke_options = None;
         self
     }
 
+    pub fn with_fill_rule(mut self, fill_rule: FillRule) -> Self {
+        self.fill_rule = fill_rule;
+        self
+    }
+
     pub fn with_stroke(mut self, stroke_width: f32) -> Self {
-        self.stroke_options = self
+        self.fill = false;
+        let stroke_options = self
             .stroke_options
+            .take()
             .map(|stroke_options| stroke_options.with_stroke_width(stroke_width))
-            .or_else(|| Some(StrokeOptions::new(stroke_width)));
+            .unwrap_or_else(|| StrokeOptions::new(stroke_width));
+        self.stroke_options = Some(self.stroke_style.apply(stroke_options));
         self
     }
 
+    /// Strokes with `stroke_options`, apart from any caps, joins, miter limit or dash pattern
+    /// set with the methods of the same name, which take precedence.
     pub fn with_stroke_opts(mut self, stroke_options: StrokeOptions) -> Self {
-        self.stroke_options = Some(stroke_options);
+        self.fill = false;
+        self.stroke_options = Some(self.stroke_style.apply(stroke_options));
         self
     }
 
+    /// Fills the shape and strokes its outline into the same `Poly`.
+    ///
+    /// The fill is tessellated first, so the outline is drawn on top of it.
+    pub fn with_fill_and_stroke(self, stroke_width: f32) -> Self {
+        let mut this = self.with_stroke(stroke_width);
+        this.fill = true;
+        this
+    }
+
+    pub fn with_fill_and_stroke_opts(self, stroke_options: StrokeOptions) -> Self {
+        let mut this = self.with_stroke_opts(stroke_options);
+        this.fill = true;
+        this
+    }
+
     pub fn with_tolerance(mut self, tolerance: f32) -> Self {
         self.tolerance = tolerance;
+        self.screen_tolerance = None;
         self
     }
 
+    /// Derives the tolerance from the maximum on-screen `error` and the `view_scale`.
+    ///
+    /// If you have a view transform, its `Transform::max_scale` is a good `view_scale`. Small
+    /// shapes get a finer tolerance, so they don't collapse into a few triangles.
+    pub fn with_screen_tolerance(mut self, error: f32, view_scale: f32) -> Self {
+        self.screen_tolerance = Some(ScreenTolerance { error, view_scale });
+        self
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), InvalidInput> {
+        validate::positive("tolerance", self.tolerance)?;
+        if let Some(ScreenTolerance { error, view_scale }) = self.screen_tolerance {
+            validate::positive("screen tolerance error", error)?;
+            validate::positive("view_scale", view_scale)?;
+        }
+        if let Some(fringe_width) = self.anti_aliasing {
+            validate::non_negative("fringe_width", fringe_width)?;
+        }
+        self.transform
+            .0
+            .to_array()
+            .iter()
+            .try_for_each(|&value| validate::finite("transform", value))?;
+        if let Some(stroke_options) = &self.stroke_options {
+            stroke_options.validate()?;
+        }
+        Ok(())
+    }
+
+    /// Applies `screen_tolerance`, if there is one.
+    pub(crate) fn resolve_tolerance(&mut self, bounding_rect: impl FnOnce() -> Rect) {
+        if let Some(ScreenTolerance { error, view_scale }) = self.screen_tolerance {
+            let size = bounding_rect().size();
+            let size = size.width.max(size.height) * self.transform.max_scale();
+            let tolerance = error / view_scale;
+            self.tolerance = if size > 0.0 {
+                tolerance.min(size * MAX_RELATIVE_TOLERANCE)
+            } else {
+                tolerance
+            };
+        }
+    }
+
+    /// Colors every vertex, for use with `ColoredVertexConstructor` or your own
+    /// `VertexConstructor`.
+    pub fn with_paint(mut self, paint: Paint) -> Self {
+        self.paint = Some(paint);
+        self
+    }
+
+    /// Surrounds the shape with a strip `fringe_width` wide, fading vertex coverage from 1 to 0.
+    ///
+    /// The fringe is added outside of the shape, so it grows by `fringe_width` on every side.
+    /// Coverage is available to `VertexConstructor`s, and `ColoredVertexConstructor` multiplies
+    /// it into alpha.
+    pub fn with_anti_aliasing(mut self, fringe_width: f32) -> Self {
+        self.anti_aliasing = Some(fringe_width);
+        self
+    }
+
+    /// Transforms the path before tessellating it.
+    ///
+    /// Stroke widths, dash patterns, fringe widths and the tolerance all apply after the
+    /// transform, while texture coordinates and `Paint` follow the shape.
+    pub fn with_transform(mut self, transform: Transform) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    /// Updates the stroke style, which applies to the current stroke as well as any set later.
+    ///
+    /// This doesn't turn stroking on by itself.
+    fn map_stroke_style(mut self, f: impl FnOnce(&mut StrokeStyle)) -> Self {
+        f(&mut self.stroke_style);
+        self.stroke_options = self
+            .stroke_options
+            .map(|stroke_options| self.stroke_style.apply(stroke_options));
+        self
+    }
+
+    /// Sets both the start and end caps of the stroke, whether it's set before or after.
+    pub fn with_line_cap(self, cap: LineCap) -> Self {
+        self.with_start_cap(cap).with_end_cap(cap)
+    }
+
+    pub fn with_start_cap(self, start_cap: LineCap) -> Self {
+        self.map_stroke_style(|style| style.start_cap = Some(start_cap))
+    }
+
+    pub fn with_end_cap(self, end_cap: LineCap) -> Self {
+        self.map_stroke_style(|style| style.end_cap = Some(end_cap))
+    }
+
+    pub fn with_line_join(self, line_join: LineJoin) -> Self {
+        self.map_stroke_style(|style| style.line_join = Some(line_join))
+    }
+
+    pub fn with_miter_limit(self, miter_limit: f32) -> Self {
+        self.map_stroke_style(|style| style.miter_limit = Some(miter_limit))
+    }
+
+    pub fn with_dash_pattern(self, dash_pattern: DashPattern) -> Self {
+        self.map_stroke_style(|style| style.dash_pattern = Some(dash_pattern))
+    }
+
     pub(crate) fn fill_options(&self) -> tess::FillOptions {
-        assert!(self.stroke_options.is_none());
-        tess::FillOptions::default().with_tolerance(self.tolerance)
+        assert!(self.fill);
+        tess::FillOptions::default()
+            .with_tolerance(self.tolerance)
+            .with_fill_rule(self.fill_rule.into())
     }
 
     pub(crate) fn stroke_options(&self) -> tess::StrokeOptions {
         let StrokeOptions {
             stroke_width,
             texture_aspect_ratio: _,
+            start_cap,
+            end_cap,
+            line_join,
+            miter_limit,
+            dash_pattern: _,
         } = self.stroke_options.clone().unwrap();
         tess::StrokeOptions::default()
             .with_tolerance(self.tolerance)
             .with_line_width(stroke_width)
+            .with_start_cap(start_cap.into())
+            .with_end_cap(end_cap.into())
+            .with_line_join(line_join.into())
+            .with_miter_limit(miter_limit)
     }
 }
 
 macro_rules! _options_forwarder {
-    ($(#[$attr:meta])*$name:ident($value:ident: $t:ty)) => {
+    ($(#[$attr:meta])*$name:ident($($value:ident: $t:ty),*)) => {
         $(#[$attr])*
-        pub fn $name(mut self, $value: $t) -> Self {
-            self.options = self.options.$name($value);
+        pub fn $name(mut self, $($value: $t),*) -> Self {
+            self.options = self.options.$name($($value),*);
             self
         }
     };
-    ($($name:ident($value:ident: $t:ty)),* $(,)*) => {
-        $(_options_forwarder!{$name($value:$t)})*
+    ($($name:ident($($value:ident: $t:ty),*)),* $(,)*) => {
+        $(_options_forwarder!{$name($($value:$t),*)})*
     };
 }
 
@@ -114,7 +444,13 @@ macro_rules! stroke {
             with_stroke(stroke_width: f32),
             with_stroke_opts(stroke_options: StrokeOptions),
             with_tolerance(tolerance: f32),
+            with_screen_tolerance(error: f32, view_scale: f32),
+            with_paint(paint: $crate::Paint),
+            with_anti_aliasing(fringe_width: f32),
+            with_transform(transform: $crate::Transform),
         }
+
+        stroke!(style);
     };
 
     (private) => {
@@ -130,6 +466,23 @@ macro_rules! stroke {
 
         _options_forwarder! {
             with_tolerance(tolerance: f32),
+            with_screen_tolerance(error: f32, view_scale: f32),
+            with_paint(paint: $crate::Paint),
+            with_anti_aliasing(fringe_width: f32),
+            with_transform(transform: $crate::Transform),
+        }
+
+        stroke!(style);
+    };
+
+    (style) => {
+        _options_forwarder! {
+            with_line_cap(cap: $crate::LineCap),
+            with_start_cap(start_cap: $crate::LineCap),
+            with_end_cap(end_cap: $crate::LineCap),
+            with_line_join(line_join: $crate::LineJoin),
+            with_miter_limit(miter_limit: f32),
+            with_dash_pattern(dash_pattern: $crate::DashPattern),
         }
     };
 }
@@ -140,5 +493,67 @@ macro_rules! fill {
             self.options = self.options.with_fill();
             self
         }
+
+        _options_forwarder! {
+            with_fill_rule(fill_rule: $crate::FillRule),
+            with_fill_and_stroke(stroke_width: f32),
+            with_fill_and_stroke_opts(stroke_options: StrokeOptions),
+        }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Error, LineSegmentBuilder, RoundRectBuilder};
+
+    #[test]
+    fn style_keeps_fill() {
+        let poly = RoundRectBuilder::default()
+            .with_line_join(LineJoin::Round)
+            .build();
+        assert!(!poly.fill_indices.is_empty());
+        assert!(poly.stroke_indices.is_empty());
+    }
+
+    #[test]
+    fn style_applies_to_later_stroke() {
+        let options = Options::default()
+            .with_line_join(LineJoin::Round)
+            .with_dash_pattern(DashPattern::dotted(2.0))
+            .with_stroke_opts(StrokeOptions::new(2.0));
+        let stroke_options = options.stroke_options.unwrap();
+        assert_eq!(stroke_options.stroke_width, 2.0);
+        assert_eq!(stroke_options.line_join, LineJoin::Round);
+        assert!(stroke_options.dash_pattern.is_some());
+    }
+
+    #[test]
+    fn style_applies_to_earlier_stroke() {
+        let options = Options::default()
+            .with_fill_and_stroke(2.0)
+            .with_line_cap(LineCap::Round);
+        assert!(options.fill);
+        let stroke_options = options.stroke_options.unwrap();
+        assert_eq!(stroke_options.start_cap, LineCap::Round);
+        assert_eq!(stroke_options.end_cap, LineCap::Round);
+    }
+
+    #[test]
+    fn zero_stroke_width() {
+        let result = RoundRectBuilder::default()
+            .with_fill_and_stroke(0.0)
+            .try_build();
+        assert!(matches!(
+            result,
+            Err(Error::InvalidInput {
+                source: InvalidInput::OutOfRange {
+                    name: "stroke_width",
+                    ..
+                },
+                ..
+            })
+        ));
+        assert!(LineSegmentBuilder::default().try_build().is_ok());
+    }
+}
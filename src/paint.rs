@@ -0,0 +1,188 @@
+use crate::tess;
+use gee::{Point, Rect, Vector};
+
+/// Linear RGBA.
+pub type Color = [f32; 4];
+
+pub const WHITE: Color = [1.0, 1.0, 1.0, 1.0];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GradientStop {
+    pub offset: f32,
+    pub color: Color,
+}
+
+impl GradientStop {
+    pub fn new(offset: f32, color: Color) -> Self {
+        Self { offset, color }
+    }
+}
+
+/// How to color vertices.
+///
+/// Gradient coordinates are relative to the builder's bounding rect, so `(0, 0)` is its top
+/// left corner and `(1, 1)` is its bottom right corner. If the bounding rect has no width or
+/// height, every position along that axis is `0`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Paint {
+    Solid(Color),
+    LinearGradient {
+        start: Point,
+        end: Point,
+        stops: Vec<GradientStop>,
+    },
+    RadialGradient {
+        center: Point,
+        radius: f32,
+        stops: Vec<GradientStop>,
+    },
+}
+
+fn sorted(stops: impl IntoIterator<Item = GradientStop>) -> Vec<GradientStop> {
+    let mut stops = stops.into_iter().collect::<Vec<_>>();
+    stops.sort_by(|a, b| a.offset.total_cmp(&b.offset));
+    stops
+}
+
+fn lerp(a: Color, b: Color, t: f32) -> Color {
+    let mut color = a;
+    for (channel, b) in color.iter_mut().zip(b) {
+        *channel += (b - *channel) * t;
+    }
+    color
+}
+
+fn sample(stops: &[GradientStop], offset: f32) -> Color {
+    match stops.iter().position(|stop| stop.offset > offset) {
+        None => stops.last().map_or(WHITE, |stop| stop.color),
+        Some(0) => stops[0].color,
+        Some(next) => {
+            let (a, b) = (stops[next - 1], stops[next]);
+            lerp(
+                a.color,
+                b.color,
+                (offset - a.offset) / (b.offset - a.offset),
+            )
+        }
+    }
+}
+
+impl Paint {
+    pub fn solid(color: Color) -> Self {
+        Self::Solid(color)
+    }
+
+    pub fn linear_gradient(
+        start: Point,
+        end: Point,
+        stops: impl IntoIterator<Item = GradientStop>,
+    ) -> Self {
+        Self::LinearGradient {
+            start,
+            end,
+            stops: sorted(stops),
+        }
+    }
+
+    pub fn radial_gradient(
+        center: Point,
+        radius: f32,
+        stops: impl IntoIterator<Item = GradientStop>,
+    ) -> Self {
+        Self::RadialGradient {
+            center,
+            radius,
+            stops: sorted(stops),
+        }
+    }
+
+    /// The color at `pos`, which is relative to the bounding rect.
+    pub fn color_at(&self, pos: Point) -> Color {
+        let pos = tess::math::Point::from(pos);
+        match self {
+            Self::Solid(color) => *color,
+            Self::LinearGradient { start, end, stops } => {
+                let start = tess::math::Point::from(*start);
+                let axis = tess::math::Point::from(*end) - start;
+                let offset = (pos - start).dot(axis) / axis.square_length();
+                sample(stops, offset)
+            }
+            Self::RadialGradient {
+                center,
+                radius,
+                stops,
+            } => sample(
+                stops,
+                (pos - tess::math::Point::from(*center)).length() / radius,
+            ),
+        }
+    }
+}
+
+/// Applies a `Paint` to absolute vertex positions.
+#[derive(Clone, Debug)]
+pub(crate) struct Painter {
+    paint: Option<Paint>,
+    // cached version of gee::Rect in the format we want
+    top_left: Point,
+    scale: Vector,
+}
+
+impl Painter {
+    pub fn new(paint: Option<Paint>, bounding_rect: impl FnOnce() -> Rect) -> Self {
+        // solid colors don't need a bounding rect, which isn't always available
+        let bounding_rect = match paint {
+            Some(Paint::LinearGradient { .. } | Paint::RadialGradient { .. }) => bounding_rect(),
+            _ => Rect::default(),
+        };
+        Self {
+            paint,
+            top_left: bounding_rect.top_left(),
+            // a straight line has no extent along one axis, which would otherwise be infinitely
+            // scaled and turn every position into NaN
+            scale: bounding_rect.size().to_vector().map(|length| {
+                if length > 0.0 {
+                    length.recip()
+                } else {
+                    0.0
+                }
+            }),
+        }
+    }
+
+    pub fn color(&self, pos: Point) -> Color {
+        self.paint.as_ref().map_or(WHITE, |paint| {
+            paint.color_at((pos - self.top_left).to_point() * self.scale)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gradient_along_straight_line() {
+        let black = [0.0, 0.0, 0.0, 1.0];
+        let gradient = Paint::linear_gradient(
+            Point::new(0.0, 0.5),
+            Point::new(1.0, 0.5),
+            [GradientStop::new(0.0, black), GradientStop::new(1.0, WHITE)],
+        );
+        let horizontal = Painter::new(Some(gradient.clone()), || {
+            Rect::from_points(Point::new(0.0, 10.0), Point::new(100.0, 10.0))
+        });
+        assert_eq!(horizontal.color(Point::new(0.0, 10.0)), black);
+        assert_eq!(
+            horizontal.color(Point::new(50.0, 10.0)),
+            [0.5, 0.5, 0.5, 1.0]
+        );
+        assert_eq!(horizontal.color(Point::new(100.0, 10.0)), WHITE);
+
+        // along the other axis, the whole line sits at the start
+        let vertical = Painter::new(Some(gradient), || {
+            Rect::from_points(Point::new(10.0, 0.0), Point::new(10.0, 100.0))
+        });
+        assert_eq!(vertical.color(Point::new(10.0, 50.0)), black);
+    }
+}
@@ -0,0 +1,454 @@
+use crate::{
+    flatten::Contour,
+    options::{Options, StrokeOptions},
+    tess, validate, Error, InvalidInput, PolyBuilder,
+};
+use gee::{Angle, Point, Rect};
+use tess::path::EndpointId;
+
+type Box2D = tess::geom::euclid::default::Box2D<f32>;
+
+#[derive(Clone, Copy, Debug)]
+pub enum PathSegment {
+    Line {
+        to: Point,
+    },
+    Quadratic {
+        ctrl: Point,
+        to: Point,
+    },
+    Cubic {
+        ctrl1: Point,
+        ctrl2: Point,
+        to: Point,
+    },
+    /// A circular arc around `center`, starting from the current point.
+    Arc {
+        center: Point,
+        sweep: Angle,
+    },
+    /// An elliptical arc from the current point to `to`, as specified by SVG.
+    SvgArc {
+        radius_x: f32,
+        radius_y: f32,
+        x_rotation: Angle,
+        large_arc: bool,
+        sweep: bool,
+        to: Point,
+    },
+}
+
+#[derive(Clone, Debug)]
+struct SubPath {
+    start: Point,
+    segments: Vec<PathSegment>,
+    closed: bool,
+}
+
+/// A path made of any number of sub-paths, each mixing lines, arcs and curves.
+#[derive(Clone, Debug, Default)]
+pub struct PathBuilder {
+    sub_paths: Vec<SubPath>,
+    options: Options,
+}
+
+impl PathBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the path `poly` would draw, keeping its options.
+    pub(crate) fn try_from_builder<T: PolyBuilder>(poly: T) -> Result<Self, Error> {
+        poly.validate().map_err(|source| Error::InvalidInput {
+            builder: crate::builder_name::<T>(),
+            source,
+        })?;
+        let mut recorder = Recorder {
+            path: Self {
+                sub_paths: Vec::new(),
+                options: poly.options().clone(),
+            },
+            next_endpoint_id: 0,
+        };
+        poly.build(&mut recorder);
+        Ok(recorder.path)
+    }
+
+    /// A path made of closed polygons.
+    pub(crate) fn from_polygons(polygons: Vec<Contour>, options: Options) -> Self {
+        Self {
+            sub_paths: polygons
+                .into_iter()
+                .filter_map(|polygon| {
+                    let (start, rest) = polygon.split_first()?;
+                    Some(SubPath {
+                        start: (*start).into(),
+                        segments: rest
+                            .iter()
+                            .map(|&to| PathSegment::Line { to: to.into() })
+                            .collect(),
+                        closed: true,
+                    })
+                })
+                .collect(),
+            options,
+        }
+    }
+
+    fn current_sub_path(&mut self) -> &mut SubPath {
+        if self.sub_paths.is_empty() {
+            self.sub_paths.push(SubPath::new(Point::zero()));
+        }
+        self.sub_paths.last_mut().unwrap()
+    }
+
+    /// Starts a new sub-path at `to`.
+    pub fn move_to(mut self, to: Point) -> Self {
+        self.sub_paths.push(SubPath::new(to));
+        self
+    }
+
+    /// Adds a segment to the current sub-path.
+    ///
+    /// If there's no current sub-path, one is started at the origin.
+    pub fn with_segment(mut self, segment: PathSegment) -> Self {
+        self.current_sub_path().segments.push(segment);
+        self
+    }
+
+    pub fn with_segments(self, segments: impl IntoIterator<Item = PathSegment>) -> Self {
+        segments
+            .into_iter()
+            .fold(self, |this, segment| this.with_segment(segment))
+    }
+
+    pub fn line_to(self, to: Point) -> Self {
+        self.with_segment(PathSegment::Line { to })
+    }
+
+    pub fn quadratic_to(self, ctrl: Point, to: Point) -> Self {
+        self.with_segment(PathSegment::Quadratic { ctrl, to })
+    }
+
+    pub fn cubic_to(self, ctrl1: Point, ctrl2: Point, to: Point) -> Self {
+        self.with_segment(PathSegment::Cubic { ctrl1, ctrl2, to })
+    }
+
+    /// Sweeps around `center` from the current point.
+    ///
+    /// Positive sweeps go clockwise in y-down coordinates.
+    pub fn arc_to(self, center: Point, sweep: impl Into<Angle>) -> Self {
+        self.with_segment(PathSegment::Arc {
+            center,
+            sweep: sweep.into(),
+        })
+    }
+
+    /// An elliptical arc to `to`, following the semantics of SVG's `A` command.
+    pub fn svg_arc_to(
+        self,
+        radius_x: f32,
+        radius_y: f32,
+        x_rotation: impl Into<Angle>,
+        large_arc: bool,
+        sweep: bool,
+        to: Point,
+    ) -> Self {
+        self.with_segment(PathSegment::SvgArc {
+            radius_x,
+            radius_y,
+            x_rotation: x_rotation.into(),
+            large_arc,
+            sweep,
+            to,
+        })
+    }
+
+    /// Closes the current sub-path.
+    ///
+    /// Subsequent segments continue from the start of the closed sub-path.
+    pub fn close(mut self) -> Self {
+        let sub_path = self.current_sub_path();
+        sub_path.closed = true;
+        let start = sub_path.start;
+        self.move_to(start)
+    }
+
+    stroke!(public);
+
+    fill!();
+
+    build!();
+}
+
+fn arc(from: tess::math::Point, center: Point, sweep: Angle) -> tess::geom::Arc<f32> {
+    let center = tess::math::Point::from(center);
+    let radius = from - center;
+    tess::geom::Arc {
+        center,
+        radii: tess::math::vector(radius.length(), radius.length()),
+        start_angle: radius.angle_from_x_axis(),
+        sweep_angle: tess::math::Angle::radians(sweep.radians()),
+        x_rotation: tess::math::Angle::zero(),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn svg_arc(
+    from: tess::math::Point,
+    radius_x: f32,
+    radius_y: f32,
+    x_rotation: Angle,
+    large_arc: bool,
+    sweep: bool,
+    to: Point,
+) -> tess::geom::SvgArc<f32> {
+    tess::geom::SvgArc {
+        from,
+        to: to.into(),
+        radii: tess::math::vector(radius_x.abs(), radius_y.abs()),
+        x_rotation: tess::math::Angle::radians(x_rotation.radians()),
+        flags: tess::geom::ArcFlags { large_arc, sweep },
+    }
+}
+
+/// Unlike `Box2D::union`, this doesn't ignore boxes with zero width or height, such as those of
+/// straight lines.
+pub(crate) fn union_boxes(a: &Box2D, b: &Box2D) -> Box2D {
+    Box2D::from_points([a.min, a.max, b.min, b.max])
+}
+
+impl SubPath {
+    fn new(start: Point) -> Self {
+        Self {
+            start,
+            segments: Vec::new(),
+            closed: false,
+        }
+    }
+
+    fn validate(&self) -> Result<(), InvalidInput> {
+        validate::point("start", self.start)?;
+        self.segments.iter().try_for_each(|segment| match *segment {
+            PathSegment::Line { to } => validate::point("to", to),
+            PathSegment::Quadratic { ctrl, to } => {
+                validate::point("ctrl", ctrl)?;
+                validate::point("to", to)
+            }
+            PathSegment::Cubic { ctrl1, ctrl2, to } => {
+                validate::point("ctrl1", ctrl1)?;
+                validate::point("ctrl2", ctrl2)?;
+                validate::point("to", to)
+            }
+            PathSegment::Arc { center, sweep } => {
+                validate::point("center", center)?;
+                validate::angle("sweep", sweep)
+            }
+            PathSegment::SvgArc {
+                radius_x,
+                radius_y,
+                x_rotation,
+                to,
+                ..
+            } => {
+                validate::finite("radius_x", radius_x)?;
+                validate::finite("radius_y", radius_y)?;
+                validate::angle("x_rotation", x_rotation)?;
+                validate::point("to", to)
+            }
+        })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.segments.is_empty() && !self.closed
+    }
+
+    fn bounding_box(&self) -> Box2D {
+        let mut current = tess::math::Point::from(self.start);
+        let mut bounding_box = Box2D::new(current, current);
+        for segment in &self.segments {
+            let (segment_box, to) = match *segment {
+                PathSegment::Line { to } => {
+                    let to = to.into();
+                    (Box2D::from_points([current, to]), to)
+                }
+                PathSegment::Quadratic { ctrl, to } => {
+                    let segment = tess::geom::QuadraticBezierSegment {
+                        from: current,
+                        ctrl: ctrl.into(),
+                        to: to.into(),
+                    };
+                    (segment.bounding_box(), segment.to)
+                }
+                PathSegment::Cubic { ctrl1, ctrl2, to } => {
+                    let segment = tess::geom::CubicBezierSegment {
+                        from: current,
+                        ctrl1: ctrl1.into(),
+                        ctrl2: ctrl2.into(),
+                        to: to.into(),
+                    };
+                    (segment.bounding_box(), segment.to)
+                }
+                PathSegment::Arc { center, sweep } => {
+                    let arc = arc(current, center, sweep);
+                    (arc.bounding_box(), arc.to())
+                }
+                PathSegment::SvgArc {
+                    radius_x,
+                    radius_y,
+                    x_rotation,
+                    large_arc,
+                    sweep,
+                    to,
+                } => {
+                    let arc = svg_arc(
+                        current, radius_x, radius_y, x_rotation, large_arc, sweep, to,
+                    );
+                    if arc.is_straight_line() {
+                        (Box2D::from_points([arc.from, arc.to]), arc.to)
+                    } else {
+                        (arc.to_arc().bounding_box(), arc.to)
+                    }
+                }
+            };
+            bounding_box = union_boxes(&bounding_box, &segment_box);
+            current = to;
+        }
+        bounding_box
+    }
+
+    fn build<B: tess::path::traits::PathBuilder>(self, builder: &mut B) {
+        let mut current = tess::math::Point::from(self.start);
+        builder.begin(current);
+        for segment in self.segments {
+            current = match segment {
+                PathSegment::Line { to } => {
+                    builder.line_to(to.into());
+                    to.into()
+                }
+                PathSegment::Quadratic { ctrl, to } => {
+                    builder.quadratic_bezier_to(ctrl.into(), to.into());
+                    to.into()
+                }
+                PathSegment::Cubic { ctrl1, ctrl2, to } => {
+                    builder.cubic_bezier_to(ctrl1.into(), ctrl2.into(), to.into());
+                    to.into()
+                }
+                PathSegment::Arc { center, sweep } => {
+                    let arc = arc(current, center, sweep);
+                    arc.for_each_quadratic_bezier(&mut |curve| {
+                        builder.quadratic_bezier_to(curve.ctrl, curve.to);
+                    });
+                    arc.to()
+                }
+                PathSegment::SvgArc {
+                    radius_x,
+                    radius_y,
+                    x_rotation,
+                    large_arc,
+                    sweep,
+                    to,
+                } => {
+                    let arc = svg_arc(
+                        current, radius_x, radius_y, x_rotation, large_arc, sweep, to,
+                    );
+                    if arc.is_straight_line() {
+                        builder.line_to(arc.to);
+                    } else {
+                        arc.for_each_quadratic_bezier(&mut |curve| {
+                            builder.quadratic_bezier_to(curve.ctrl, curve.to);
+                        });
+                    }
+                    arc.to
+                }
+            };
+        }
+        builder.end(self.closed);
+    }
+}
+
+impl PolyBuilder for PathBuilder {
+    fn options(&self) -> &Options {
+        &self.options
+    }
+
+    fn validate(&self) -> Result<(), InvalidInput> {
+        self.sub_paths.iter().try_for_each(SubPath::validate)
+    }
+
+    fn bounding_rect(&self) -> Rect {
+        self.sub_paths
+            .iter()
+            .filter(|sub_path| !sub_path.is_empty())
+            .map(SubPath::bounding_box)
+            .reduce(|a, b| union_boxes(&a, &b))
+            .map(|bounding_box| Rect::from_points(bounding_box.min.into(), bounding_box.max.into()))
+            .unwrap_or_default()
+    }
+
+    fn build<B: tess::path::traits::PathBuilder>(self, builder: &mut B) {
+        for sub_path in self.sub_paths {
+            if !sub_path.is_empty() {
+                sub_path.build(builder);
+            }
+        }
+    }
+}
+
+/// Records everything built into it as a `PathBuilder`.
+struct Recorder {
+    path: PathBuilder,
+    next_endpoint_id: u32,
+}
+
+impl Recorder {
+    fn push(&mut self, segment: PathSegment) -> EndpointId {
+        self.path.current_sub_path().segments.push(segment);
+        self.endpoint_id()
+    }
+
+    fn endpoint_id(&mut self) -> EndpointId {
+        let id = EndpointId(self.next_endpoint_id);
+        self.next_endpoint_id += 1;
+        id
+    }
+}
+
+impl tess::path::traits::PathBuilder for Recorder {
+    fn begin(&mut self, at: tess::math::Point) -> EndpointId {
+        self.path.sub_paths.push(SubPath::new(at.into()));
+        self.endpoint_id()
+    }
+
+    fn end(&mut self, close: bool) {
+        self.path.current_sub_path().closed = close;
+    }
+
+    fn line_to(&mut self, to: tess::math::Point) -> EndpointId {
+        self.push(PathSegment::Line { to: to.into() })
+    }
+
+    fn quadratic_bezier_to(
+        &mut self,
+        ctrl: tess::math::Point,
+        to: tess::math::Point,
+    ) -> EndpointId {
+        self.push(PathSegment::Quadratic {
+            ctrl: ctrl.into(),
+            to: to.into(),
+        })
+    }
+
+    fn cubic_bezier_to(
+        &mut self,
+        ctrl1: tess::math::Point,
+        ctrl2: tess::math::Point,
+        to: tess::math::Point,
+    ) -> EndpointId {
+        self.push(PathSegment::Cubic {
+            ctrl1: ctrl1.into(),
+            ctrl2: ctrl2.into(),
+            to: to.into(),
+        })
+    }
+}
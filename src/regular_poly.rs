@@ -1,7 +1,7 @@
 use crate::{
     default_start_angle,
     options::{Options, StrokeOptions},
-    tess, FreePolyBuilder, PolyBuilder, DEFAULT_RADIUS,
+    tess, validate, FreePolyBuilder, InvalidInput, PolyBuilder, DEFAULT_RADIUS,
 };
 use gee::{Angle, Circle, Point, Rect};
 
@@ -10,6 +10,7 @@ pub struct RegularPolyBuilder {
     circle: Circle,
     sides: u32,
     start_angle: Angle,
+    corner_radius: f32,
     options: Options,
 }
 
@@ -19,6 +20,7 @@ impl Default for RegularPolyBuilder {
             circle: Circle::from_radius(DEFAULT_RADIUS),
             sides: 3,
             start_angle: default_start_angle(),
+            corner_radius: 0.0,
             options: Default::default(),
         }
     }
@@ -53,12 +55,8 @@ impl RegularPolyBuilder {
         Self::new(10)
     }
 
+    /// Values for `sides` must be at least 3.
     pub fn with_sides(mut self, sides: u32) -> Self {
-        assert!(
-            sides >= 3,
-            "regular polygons must have at least 3 sides, but this one has {}",
-            sides
-        );
         self.sides = sides;
         self
     }
@@ -77,6 +75,15 @@ impl RegularPolyBuilder {
         self
     }
 
+    /// Rounds each corner with a tangent arc of `radius`.
+    ///
+    /// The arcs are shrunk where needed to fit within half of each side. Values for `radius` must
+    /// be non-negative.
+    pub fn with_corner_radius(mut self, radius: f32) -> Self {
+        self.corner_radius = radius;
+        self
+    }
+
     stroke!(public);
 
     fill!();
@@ -89,6 +96,18 @@ impl PolyBuilder for RegularPolyBuilder {
         &self.options
     }
 
+    fn validate(&self) -> Result<(), InvalidInput> {
+        if self.sides < 3 {
+            return Err(InvalidInput::SideCount {
+                sides: self.sides,
+                min: 3,
+            });
+        }
+        validate::circle(self.circle)?;
+        validate::angle("start_angle", self.start_angle)?;
+        validate::non_negative("corner_radius", self.corner_radius)
+    }
+
     fn bounding_rect(&self) -> Rect {
         self.circle.bounding_rect()
     }
@@ -97,11 +116,25 @@ impl PolyBuilder for RegularPolyBuilder {
         PolyBuilder::build(
             FreePolyBuilder::from_parts(
                 self.circle.circle_points(self.sides, self.start_angle),
-                true,
+                false,
                 Some(self.bounding_rect()),
                 self.options,
-            ),
+            )
+            .with_corner_radii(vec![self.corner_radius]),
             builder,
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::free_poly::rounded_corners;
+
+    #[test]
+    fn rounds_every_corner() {
+        let hexagon = RegularPolyBuilder::hexagon().with_corner_radius(5.0);
+        assert_eq!(rounded_corners(hexagon.clone()), 6);
+        assert_eq!(rounded_corners(hexagon.with_stroke(2.0)), 6);
+    }
+}
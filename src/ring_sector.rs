@@ -0,0 +1,135 @@
+use crate::{
+    arc::{arc, arc_to, default_sweep},
+    default_start_angle,
+    options::{Options, StrokeOptions},
+    tess, validate, InvalidInput, PolyBuilder, DEFAULT_RADIUS,
+};
+use gee::{Angle, Circle, Point, Rect};
+
+/// A section of a ring, bounded by an inner and outer radius.
+#[derive(Clone, Debug)]
+pub struct RingSectorBuilder {
+    circle: Circle,
+    inner_radius: f32,
+    start_angle: Angle,
+    sweep: Angle,
+    options: Options,
+}
+
+impl Default for RingSectorBuilder {
+    fn default() -> Self {
+        Self {
+            circle: Circle::from_radius(DEFAULT_RADIUS),
+            inner_radius: DEFAULT_RADIUS / 2.0,
+            start_angle: default_start_angle(),
+            sweep: default_sweep(),
+            options: Default::default(),
+        }
+    }
+}
+
+impl RingSectorBuilder {
+    pub fn new(circle: Circle, inner_radius: f32, sweep: impl Into<Angle>) -> Self {
+        Self::default()
+            .with_circle(circle)
+            .with_inner_radius(inner_radius)
+            .with_sweep(sweep)
+    }
+
+    /// The circle bounding the outer edge of the ring.
+    pub fn with_circle(mut self, circle: Circle) -> Self {
+        self.circle = circle;
+        self
+    }
+
+    pub fn with_center_and_radius(self, center: Point, radius: f32) -> Self {
+        self.with_circle(Circle::new(center, radius))
+    }
+
+    pub fn with_inner_radius(mut self, inner_radius: f32) -> Self {
+        self.inner_radius = inner_radius;
+        self
+    }
+
+    pub fn with_rotation(mut self, start_angle: impl Into<Angle>) -> Self {
+        self.start_angle = start_angle.into();
+        self
+    }
+
+    /// How far the sector extends from its start angle.
+    ///
+    /// Positive sweeps go clockwise.
+    pub fn with_sweep(mut self, sweep: impl Into<Angle>) -> Self {
+        self.sweep = sweep.into();
+        self
+    }
+
+    stroke!(public);
+
+    fill!();
+
+    build!();
+}
+
+impl PolyBuilder for RingSectorBuilder {
+    fn options(&self) -> &Options {
+        &self.options
+    }
+
+    fn validate(&self) -> Result<(), InvalidInput> {
+        validate::circle(self.circle)?;
+        validate::inner_radius(self.inner_radius, self.circle.radius())?;
+        validate::angle("start_angle", self.start_angle)?;
+        validate::angle("sweep", self.sweep)
+    }
+
+    /// This is the bounding rect of the whole outer circle, so texture coordinates stay put as
+    /// the sweep changes.
+    fn bounding_rect(&self) -> Rect {
+        self.circle.bounding_rect()
+    }
+
+    fn build<B: tess::path::traits::PathBuilder>(self, builder: &mut B) {
+        let outer = arc(self.circle, self.start_angle, self.sweep);
+        let inner = arc(
+            Circle::new(self.circle.center(), self.inner_radius),
+            self.start_angle + self.sweep,
+            Angle::from_radians(-self.sweep.radians()),
+        );
+        builder.begin(outer.from());
+        arc_to(builder, &outer);
+        builder.line_to(inner.from());
+        arc_to(builder, &inner);
+        builder.end(true);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, InvalidInput, RingSectorBuilder};
+    use gee::{Angle, Circle};
+
+    #[test]
+    fn inner_radius_inside_radius() {
+        for inner_radius in [10.0, 20.0] {
+            let result =
+                RingSectorBuilder::new(Circle::from_radius(10.0), inner_radius, Angle::PI())
+                    .try_build();
+            assert!(matches!(
+                result,
+                Err(Error::InvalidInput {
+                    source: InvalidInput::OutOfRange {
+                        name: "inner_radius",
+                        ..
+                    },
+                    ..
+                })
+            ));
+        }
+        assert!(
+            RingSectorBuilder::new(Circle::from_radius(10.0), 5.0, Angle::PI())
+                .try_build()
+                .is_ok()
+        );
+    }
+}
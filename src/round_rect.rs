@@ -1,6 +1,6 @@
 use crate::{
     options::{Options, StrokeOptions},
-    tess, PolyBuilder,
+    tess, validate, InvalidInput, PolyBuilder,
 };
 use gee::{Point, Rect, Size};
 
@@ -108,6 +108,14 @@ impl PolyBuilder for RoundRectBuilder {
         &self.options
     }
 
+    fn validate(&self) -> Result<(), InvalidInput> {
+        validate::rect(self.rect)?;
+        validate::non_negative("top_left_radius", self.top_left_radius)?;
+        validate::non_negative("top_right_radius", self.top_right_radius)?;
+        validate::non_negative("bottom_right_radius", self.bottom_right_radius)?;
+        validate::non_negative("bottom_left_radius", self.bottom_left_radius)
+    }
+
     fn bounding_rect(&self) -> Rect {
         self.rect
     }
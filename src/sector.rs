@@ -0,0 +1,87 @@
+use crate::{
+    arc::{arc, arc_to, default_sweep},
+    default_start_angle,
+    options::{Options, StrokeOptions},
+    tess, validate, InvalidInput, PolyBuilder, DEFAULT_RADIUS,
+};
+use gee::{Angle, Circle, Point, Rect};
+
+/// A pie slice.
+#[derive(Clone, Debug)]
+pub struct SectorBuilder {
+    circle: Circle,
+    start_angle: Angle,
+    sweep: Angle,
+    options: Options,
+}
+
+impl Default for SectorBuilder {
+    fn default() -> Self {
+        Self {
+            circle: Circle::from_radius(DEFAULT_RADIUS),
+            start_angle: default_start_angle(),
+            sweep: default_sweep(),
+            options: Default::default(),
+        }
+    }
+}
+
+impl SectorBuilder {
+    pub fn new(circle: Circle, sweep: impl Into<Angle>) -> Self {
+        Self::default().with_circle(circle).with_sweep(sweep)
+    }
+
+    pub fn with_circle(mut self, circle: Circle) -> Self {
+        self.circle = circle;
+        self
+    }
+
+    pub fn with_center_and_radius(self, center: Point, radius: f32) -> Self {
+        self.with_circle(Circle::new(center, radius))
+    }
+
+    pub fn with_rotation(mut self, start_angle: impl Into<Angle>) -> Self {
+        self.start_angle = start_angle.into();
+        self
+    }
+
+    /// How far the sector extends from its start angle.
+    ///
+    /// Positive sweeps go clockwise.
+    pub fn with_sweep(mut self, sweep: impl Into<Angle>) -> Self {
+        self.sweep = sweep.into();
+        self
+    }
+
+    stroke!(public);
+
+    fill!();
+
+    build!();
+}
+
+impl PolyBuilder for SectorBuilder {
+    fn options(&self) -> &Options {
+        &self.options
+    }
+
+    fn validate(&self) -> Result<(), InvalidInput> {
+        validate::circle(self.circle)?;
+        validate::angle("start_angle", self.start_angle)?;
+        validate::angle("sweep", self.sweep)
+    }
+
+    /// This is the bounding rect of the whole circle, so texture coordinates stay put as the
+    /// sweep changes.
+    fn bounding_rect(&self) -> Rect {
+        self.circle.bounding_rect()
+    }
+
+    fn build<B: tess::path::traits::PathBuilder>(self, builder: &mut B) {
+        let arc = arc(self.circle, self.start_angle, self.sweep);
+        builder.begin(arc.center);
+        builder.line_to(arc.from());
+        arc_to(builder, &arc);
+        builder.end(true);
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{
     default_start_angle,
     options::{Options, StrokeOptions},
-    tess, FreePolyBuilder, PolyBuilder, DEFAULT_RADIUS,
+    tess, validate, FreePolyBuilder, InvalidInput, PolyBuilder, DEFAULT_RADIUS,
 };
 use gee::{Angle, Circle, Point, Rect};
 use itertools::Itertools as _;
@@ -12,6 +12,8 @@ pub struct StarBuilder {
     inner_radius_over_radius: f32,
     tips: u32,
     start_angle: Angle,
+    tip_radius: f32,
+    valley_radius: f32,
     options: Options,
 }
 
@@ -22,6 +24,8 @@ impl Default for StarBuilder {
             inner_radius_over_radius: 0.5,
             tips: 5,
             start_angle: default_start_angle(),
+            tip_radius: 0.0,
+            valley_radius: 0.0,
             options: Default::default(),
         }
     }
@@ -40,12 +44,8 @@ impl StarBuilder {
         Self::new(6)
     }
 
+    /// Values for `tips` must be at least 3.
     pub fn with_tips(mut self, tips: u32) -> Self {
-        assert!(
-            tips >= 3,
-            "stars must have at least 3 tips, but this one has {}",
-            tips
-        );
         self.tips = tips;
         self
     }
@@ -68,14 +68,30 @@ impl StarBuilder {
     ///
     /// Values for `inner_radius_over_radius` must be in the range (0, 1].
     pub fn with_inner_radius_ratio(mut self, inner_radius_over_radius: f32) -> Self {
-        assert!(
-            inner_radius_over_radius > 0.0 && inner_radius_over_radius <= 1.0,
-            "`inner_radius_ratio` must be in the range `(0, 1]`"
-        );
         self.inner_radius_over_radius = inner_radius_over_radius;
         self
     }
 
+    /// Rounds both the tips and the valleys between them with tangent arcs of `radius`.
+    ///
+    /// The arcs are shrunk where needed to fit within half of each edge. Values for `radius` must
+    /// be non-negative.
+    pub fn with_corner_radius(self, radius: f32) -> Self {
+        self.with_tip_radius(radius).with_valley_radius(radius)
+    }
+
+    /// Rounds the outer tips with tangent arcs of `radius`.
+    pub fn with_tip_radius(mut self, radius: f32) -> Self {
+        self.tip_radius = radius;
+        self
+    }
+
+    /// Rounds the inner valleys between tips with tangent arcs of `radius`.
+    pub fn with_valley_radius(mut self, radius: f32) -> Self {
+        self.valley_radius = radius;
+        self
+    }
+
     stroke!(public);
 
     fill!();
@@ -88,6 +104,28 @@ impl PolyBuilder for StarBuilder {
         &self.options
     }
 
+    fn validate(&self) -> Result<(), InvalidInput> {
+        if self.tips < 3 {
+            return Err(InvalidInput::TipCount {
+                tips: self.tips,
+                min: 3,
+            });
+        }
+        validate::circle(self.circle)?;
+        validate::angle("start_angle", self.start_angle)?;
+        validate::non_negative("tip_radius", self.tip_radius)?;
+        validate::non_negative("valley_radius", self.valley_radius)?;
+        let ratio = self.inner_radius_over_radius;
+        if !(ratio > 0.0 && ratio <= 1.0) {
+            return Err(InvalidInput::OutOfRange {
+                name: "inner_radius_over_radius",
+                value: ratio,
+                expected: "in the range (0, 1]",
+            });
+        }
+        Ok(())
+    }
+
     fn bounding_rect(&self) -> Rect {
         self.circle.bounding_rect()
     }
@@ -103,11 +141,26 @@ impl PolyBuilder for StarBuilder {
                         .circle_points(self.tips, top_angle)
                         .interleave(inner_circle.circle_points(self.tips, top_angle + inner_offset))
                 },
-                true,
+                false,
                 Some(self.bounding_rect()),
                 self.options,
-            ),
+            )
+            .with_corner_radii(vec![self.tip_radius, self.valley_radius]),
             builder,
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::free_poly::rounded_corners;
+
+    #[test]
+    fn rounds_every_corner() {
+        let star = StarBuilder::pentagram();
+        assert_eq!(rounded_corners(star.clone().with_corner_radius(3.0)), 10);
+        assert_eq!(rounded_corners(star.clone().with_tip_radius(3.0)), 5);
+        assert_eq!(rounded_corners(star.with_valley_radius(3.0)), 5);
+    }
+}
@@ -0,0 +1,117 @@
+use crate::Poly;
+use std::fmt::Write as _;
+
+/// Controls what `Poly::to_svg_with` draws on top of the triangles.
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct SvgOptions {
+    pub wireframe: bool,
+    pub vertex_indices: bool,
+}
+
+impl SvgOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Outlines every triangle.
+    pub fn with_wireframe(mut self) -> Self {
+        self.wireframe = true;
+        self
+    }
+
+    /// Labels every vertex with its index.
+    pub fn with_vertex_indices(mut self) -> Self {
+        self.vertex_indices = true;
+        self
+    }
+}
+
+const FILL_COLOR: &str = "#4a90d9";
+const STROKE_COLOR: &str = "#d98a4a";
+
+impl Poly {
+    /// Renders this `Poly`'s triangles as an SVG document, for debugging.
+    pub fn to_svg(&self) -> String {
+        self.to_svg_with(&SvgOptions::default())
+    }
+
+    pub fn to_svg_with(&self, options: &SvgOptions) -> String {
+        let (min_x, min_y, max_x, max_y) = self.vertices.iter().fold(
+            (
+                f32::INFINITY,
+                f32::INFINITY,
+                f32::NEG_INFINITY,
+                f32::NEG_INFINITY,
+            ),
+            |(min_x, min_y, max_x, max_y), vertex| {
+                (
+                    min_x.min(vertex.pos.x),
+                    min_y.min(vertex.pos.y),
+                    max_x.max(vertex.pos.x),
+                    max_y.max(vertex.pos.y),
+                )
+            },
+        );
+        let (min_x, min_y, width, height) = if self.vertices.is_empty() {
+            (0.0, 0.0, 1.0, 1.0)
+        } else {
+            (min_x, min_y, max_x - min_x, max_y - min_y)
+        };
+        // keeps lines and labels legible regardless of the `Poly`'s size
+        let unit = width.max(height).max(f32::EPSILON) / 200.0;
+        let margin = unit * 10.0;
+
+        let mut svg = String::new();
+        // writing to a `String` can't fail
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {} {}">"#,
+            min_x - margin,
+            min_y - margin,
+            width + margin * 2.0,
+            height + margin * 2.0,
+        );
+        let _ = writeln!(
+            svg,
+            r#"<g stroke="{}" stroke-width="{}" stroke-linejoin="round" fill-opacity="0.5">"#,
+            if options.wireframe { "black" } else { "none" },
+            unit * 0.5,
+        );
+        for (nth, triangle) in self.indices.chunks_exact(3).enumerate() {
+            let color = if self.stroke_indices.contains(&(nth * 3)) {
+                STROKE_COLOR
+            } else {
+                FILL_COLOR
+            };
+            let _ = write!(svg, r#"<polygon fill="{}" points=""#, color);
+            for &index in triangle {
+                let pos = self.vertices[index as usize].pos;
+                let _ = write!(svg, "{},{} ", pos.x, pos.y);
+            }
+            let _ = writeln!(svg, r#""/>"#);
+        }
+        let _ = writeln!(svg, "</g>");
+        if options.vertex_indices {
+            let _ = writeln!(
+                svg,
+                r#"<g font-family="monospace" font-size="{}" text-anchor="middle">"#,
+                unit * 4.0,
+            );
+            for (index, vertex) in self.vertices.iter().enumerate() {
+                let _ = writeln!(
+                    svg,
+                    r#"<circle cx="{x}" cy="{y}" r="{r}"/><text x="{x}" y="{text_y}">{index}</text>"#,
+                    x = vertex.pos.x,
+                    y = vertex.pos.y,
+                    r = unit,
+                    text_y = vertex.pos.y - unit * 1.5,
+                    index = index,
+                );
+            }
+            let _ = writeln!(svg, "</g>");
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
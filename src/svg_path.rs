@@ -0,0 +1,410 @@
+use crate::{Error, PathBuilder};
+use gee::{Angle, Point};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum SvgPathError {
+    #[error("expected a command")]
+    ExpectedCommand,
+    #[error("expected a number")]
+    ExpectedNumber,
+    #[error("expected an arc flag (`0` or `1`)")]
+    ExpectedFlag,
+    #[error("path data must start with a move command")]
+    MissingMoveTo,
+}
+
+struct Parser<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, source: SvgPathError) -> Error {
+        Error::SvgPath {
+            position: self.position,
+            source,
+        }
+    }
+
+    fn skip_separators(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r' | b'\x0C' | b',') = self.peek() {
+            self.position += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.data.get(self.position).copied()
+    }
+
+    fn at_number(&mut self) -> bool {
+        self.skip_separators();
+        matches!(self.peek(), Some(b'0'..=b'9' | b'-' | b'+' | b'.'))
+    }
+
+    fn digits(&mut self) -> usize {
+        let start = self.position;
+        while let Some(b'0'..=b'9') = self.peek() {
+            self.position += 1;
+        }
+        self.position - start
+    }
+
+    fn number(&mut self) -> Result<f32, Error> {
+        self.skip_separators();
+        let start = self.position;
+        if let Some(b'-' | b'+') = self.peek() {
+            self.position += 1;
+        }
+        let mut digits = self.digits();
+        if self.peek() == Some(b'.') {
+            self.position += 1;
+            digits += self.digits();
+        }
+        if digits == 0 {
+            self.position = start;
+            return Err(self.error(SvgPathError::ExpectedNumber));
+        }
+        if let Some(b'e' | b'E') = self.peek() {
+            let mantissa_end = self.position;
+            self.position += 1;
+            if let Some(b'-' | b'+') = self.peek() {
+                self.position += 1;
+            }
+            if self.digits() == 0 {
+                self.position = mantissa_end;
+            }
+        }
+        std::str::from_utf8(&self.data[start..self.position])
+            .ok()
+            .and_then(|number| number.parse().ok())
+            .ok_or_else(|| {
+                self.position = start;
+                self.error(SvgPathError::ExpectedNumber)
+            })
+    }
+
+    fn point(&mut self) -> Result<Point, Error> {
+        Ok(Point::new(self.number()?, self.number()?))
+    }
+
+    fn flag(&mut self) -> Result<bool, Error> {
+        self.skip_separators();
+        let flag = match self.peek() {
+            Some(b'0') => false,
+            Some(b'1') => true,
+            _ => return Err(self.error(SvgPathError::ExpectedFlag)),
+        };
+        self.position += 1;
+        Ok(flag)
+    }
+}
+
+fn offset(point: Point, origin: Point, relative: bool) -> Point {
+    if relative {
+        Point::new(origin.x + point.x, origin.y + point.y)
+    } else {
+        point
+    }
+}
+
+fn reflect(point: Point, center: Point) -> Point {
+    Point::new(2.0 * center.x - point.x, 2.0 * center.y - point.y)
+}
+
+impl PathBuilder {
+    /// Parses SVG path data, i.e. the contents of a `<path>`'s `d` attribute.
+    pub fn from_svg(data: &str) -> Result<Self, Error> {
+        let mut parser = Parser {
+            data: data.as_bytes(),
+            position: 0,
+        };
+        let mut path = Self::new();
+        let mut current = Point::zero();
+        let mut start = Point::zero();
+        // the last control point, for reflecting in smooth curves
+        let mut last_cubic_ctrl = None;
+        let mut last_quadratic_ctrl = None;
+        let mut command = None;
+        loop {
+            parser.skip_separators();
+            let next = match parser.peek() {
+                Some(next) => next,
+                None => break,
+            };
+            if next.is_ascii_alphabetic() {
+                if command.is_none() && !matches!(next, b'M' | b'm') {
+                    return Err(parser.error(SvgPathError::MissingMoveTo));
+                }
+                parser.position += 1;
+                command = Some(next);
+            } else if command.is_none() {
+                return Err(parser.error(SvgPathError::MissingMoveTo));
+            } else if !parser.at_number() || matches!(command, Some(b'Z' | b'z')) {
+                // only numbers can follow a command, and `Z` doesn't take any
+                return Err(parser.error(SvgPathError::ExpectedCommand));
+            }
+            let command = command.unwrap();
+            let relative = command.is_ascii_lowercase();
+            let (cubic_ctrl, quadratic_ctrl) = match command.to_ascii_uppercase() {
+                b'M' => {
+                    current = offset(parser.point()?, current, relative);
+                    start = current;
+                    path = path.move_to(current);
+                    // subsequent coordinate pairs are implicit line-tos
+                    while parser.at_number() {
+                        current = offset(parser.point()?, current, relative);
+                        path = path.line_to(current);
+                    }
+                    (None, None)
+                }
+                b'L' => {
+                    current = offset(parser.point()?, current, relative);
+                    path = path.line_to(current);
+                    (None, None)
+                }
+                b'H' => {
+                    let x = parser.number()?;
+                    current = Point::new(if relative { current.x + x } else { x }, current.y);
+                    path = path.line_to(current);
+                    (None, None)
+                }
+                b'V' => {
+                    let y = parser.number()?;
+                    current = Point::new(current.x, if relative { current.y + y } else { y });
+                    path = path.line_to(current);
+                    (None, None)
+                }
+                b'C' => {
+                    let ctrl1 = offset(parser.point()?, current, relative);
+                    let ctrl2 = offset(parser.point()?, current, relative);
+                    current = offset(parser.point()?, current, relative);
+                    path = path.cubic_to(ctrl1, ctrl2, current);
+                    (Some(ctrl2), None)
+                }
+                b'S' => {
+                    let ctrl1 = last_cubic_ctrl.map_or(current, |ctrl| reflect(ctrl, current));
+                    let ctrl2 = offset(parser.point()?, current, relative);
+                    current = offset(parser.point()?, current, relative);
+                    path = path.cubic_to(ctrl1, ctrl2, current);
+                    (Some(ctrl2), None)
+                }
+                b'Q' => {
+                    let ctrl = offset(parser.point()?, current, relative);
+                    current = offset(parser.point()?, current, relative);
+                    path = path.quadratic_to(ctrl, current);
+                    (None, Some(ctrl))
+                }
+                b'T' => {
+                    let ctrl = last_quadratic_ctrl.map_or(current, |ctrl| reflect(ctrl, current));
+                    current = offset(parser.point()?, current, relative);
+                    path = path.quadratic_to(ctrl, current);
+                    (None, Some(ctrl))
+                }
+                b'A' => {
+                    let radius_x = parser.number()?;
+                    let radius_y = parser.number()?;
+                    let x_rotation = Angle::from_degrees(parser.number()?);
+                    let large_arc = parser.flag()?;
+                    let sweep = parser.flag()?;
+                    current = offset(parser.point()?, current, relative);
+                    path =
+                        path.svg_arc_to(radius_x, radius_y, x_rotation, large_arc, sweep, current);
+                    (None, None)
+                }
+                b'Z' => {
+                    current = start;
+                    path = path.close();
+                    (None, None)
+                }
+                _ => {
+                    parser.position -= 1;
+                    return Err(parser.error(SvgPathError::ExpectedCommand));
+                }
+            };
+            last_cubic_ctrl = cubic_ctrl;
+            last_quadratic_ctrl = quadratic_ctrl;
+        }
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_parses(data: &str, expected: PathBuilder) {
+        let path = PathBuilder::from_svg(data).unwrap();
+        assert_eq!(format!("{path:?}"), format!("{expected:?}"), "{data}");
+    }
+
+    fn assert_fails(data: &str, expected_position: usize, expected: SvgPathError) {
+        match PathBuilder::from_svg(data) {
+            Err(Error::SvgPath { position, source }) => {
+                assert_eq!((position, source), (expected_position, expected), "{data}")
+            }
+            other => panic!("{data}: expected {expected:?}, got {other:?}"),
+        }
+    }
+
+    fn p(x: f32, y: f32) -> Point {
+        Point::new(x, y)
+    }
+
+    #[test]
+    fn absolute_commands() {
+        assert_parses(
+            "M10 20 L30 40 H50 V60 C1 2 3 4 5 6 S7 8 9 10 Q11 12 13 14 T15 16 A5 6 30 1 0 17 18 Z",
+            PathBuilder::new()
+                .move_to(p(10.0, 20.0))
+                .line_to(p(30.0, 40.0))
+                .line_to(p(50.0, 40.0))
+                .line_to(p(50.0, 60.0))
+                .cubic_to(p(1.0, 2.0), p(3.0, 4.0), p(5.0, 6.0))
+                .cubic_to(p(7.0, 8.0), p(7.0, 8.0), p(9.0, 10.0))
+                .quadratic_to(p(11.0, 12.0), p(13.0, 14.0))
+                .quadratic_to(p(15.0, 16.0), p(15.0, 16.0))
+                .svg_arc_to(
+                    5.0,
+                    6.0,
+                    Angle::from_degrees(30.0),
+                    true,
+                    false,
+                    p(17.0, 18.0),
+                )
+                .close(),
+        );
+    }
+
+    #[test]
+    fn relative_commands() {
+        assert_parses(
+            "m10 20 l30 40 h50 v60 c1 2 3 4 5 6 s7 8 9 10 q11 12 13 14 t15 16 a5 6 30 1 0 17 18 z \
+             m1 1 l1 0",
+            PathBuilder::new()
+                .move_to(p(10.0, 20.0))
+                .line_to(p(40.0, 60.0))
+                .line_to(p(90.0, 60.0))
+                .line_to(p(90.0, 120.0))
+                .cubic_to(p(91.0, 122.0), p(93.0, 124.0), p(95.0, 126.0))
+                .cubic_to(p(97.0, 128.0), p(102.0, 134.0), p(104.0, 136.0))
+                .quadratic_to(p(115.0, 148.0), p(117.0, 150.0))
+                .quadratic_to(p(119.0, 152.0), p(132.0, 166.0))
+                .svg_arc_to(
+                    5.0,
+                    6.0,
+                    Angle::from_degrees(30.0),
+                    true,
+                    false,
+                    p(149.0, 184.0),
+                )
+                .close()
+                .move_to(p(11.0, 21.0))
+                .line_to(p(12.0, 21.0)),
+        );
+    }
+
+    #[test]
+    fn implicit_commands() {
+        let lines = || {
+            PathBuilder::new()
+                .move_to(p(1.0, 2.0))
+                .line_to(p(3.0, 4.0))
+                .line_to(p(5.0, 6.0))
+        };
+        assert_parses("M1 2 3 4 5 6", lines());
+        assert_parses("m1 2 2 2 2 2", lines());
+        assert_parses("M1 2 L3 4 5 6", lines());
+        assert_parses("M1 2 l2 2 2 2", lines());
+        assert_parses(
+            "M0 0 h1 2 v3",
+            PathBuilder::new()
+                .move_to(p(0.0, 0.0))
+                .line_to(p(1.0, 0.0))
+                .line_to(p(3.0, 0.0))
+                .line_to(p(3.0, 3.0)),
+        );
+        assert_parses("", PathBuilder::new());
+    }
+
+    #[test]
+    fn packed_numbers() {
+        assert_parses(
+            "M1.5.5L-.5e-1-2,+3E1\t2e+1\n.25.75",
+            PathBuilder::new()
+                .move_to(p(1.5, 0.5))
+                .line_to(p(-0.05, -2.0))
+                .line_to(p(30.0, 20.0))
+                .line_to(p(0.25, 0.75)),
+        );
+    }
+
+    #[test]
+    fn compact_arc_flags() {
+        assert_parses(
+            "M0 0a1 1 0 00 10 10A1,1,0,1110,10",
+            PathBuilder::new()
+                .move_to(p(0.0, 0.0))
+                .svg_arc_to(
+                    1.0,
+                    1.0,
+                    Angle::from_degrees(0.0),
+                    false,
+                    false,
+                    p(10.0, 10.0),
+                )
+                .svg_arc_to(
+                    1.0,
+                    1.0,
+                    Angle::from_degrees(0.0),
+                    true,
+                    true,
+                    p(10.0, 10.0),
+                ),
+        );
+    }
+
+    #[test]
+    fn smooth_curves_reflect_only_matching_curves() {
+        // after a command that isn't the same kind of curve, the control point is the current
+        // point
+        assert_parses(
+            "M0 0 L10 0 S20 10 30 0 Q35 5 40 0 S50 10 60 0 T70 0 C1 2 3 4 5 6 T7 8",
+            PathBuilder::new()
+                .move_to(p(0.0, 0.0))
+                .line_to(p(10.0, 0.0))
+                .cubic_to(p(10.0, 0.0), p(20.0, 10.0), p(30.0, 0.0))
+                .quadratic_to(p(35.0, 5.0), p(40.0, 0.0))
+                .cubic_to(p(40.0, 0.0), p(50.0, 10.0), p(60.0, 0.0))
+                .quadratic_to(p(60.0, 0.0), p(70.0, 0.0))
+                .cubic_to(p(1.0, 2.0), p(3.0, 4.0), p(5.0, 6.0))
+                .quadratic_to(p(5.0, 6.0), p(7.0, 8.0)),
+        );
+        // and otherwise it's reflected, through any number of smooth curves
+        assert_parses(
+            "M0 0 Q5 5 10 0 T20 0 T30 0 C30 5 35 5 40 0 S50 5 50 0 s5 -5 10 0",
+            PathBuilder::new()
+                .move_to(p(0.0, 0.0))
+                .quadratic_to(p(5.0, 5.0), p(10.0, 0.0))
+                .quadratic_to(p(15.0, -5.0), p(20.0, 0.0))
+                .quadratic_to(p(25.0, 5.0), p(30.0, 0.0))
+                .cubic_to(p(30.0, 5.0), p(35.0, 5.0), p(40.0, 0.0))
+                .cubic_to(p(45.0, -5.0), p(50.0, 5.0), p(50.0, 0.0))
+                .cubic_to(p(50.0, -5.0), p(55.0, -5.0), p(60.0, 0.0)),
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_fails("L1 2", 0, SvgPathError::MissingMoveTo);
+        assert_fails("  1 2", 2, SvgPathError::MissingMoveTo);
+        assert_fails("M1", 2, SvgPathError::ExpectedNumber);
+        assert_fails("M1 x", 3, SvgPathError::ExpectedNumber);
+        assert_fails("M1 2 L.", 6, SvgPathError::ExpectedNumber);
+        assert_fails("M1 2 L-e1 2", 6, SvgPathError::ExpectedNumber);
+        assert_fails("M0 0 A1 1 0 2 0 1 1", 12, SvgPathError::ExpectedFlag);
+        assert_fails("M0 0 A1 1 0 0", 13, SvgPathError::ExpectedFlag);
+        assert_fails("M0 0 Z 1", 7, SvgPathError::ExpectedCommand);
+        assert_fails("M0 0 X1", 5, SvgPathError::ExpectedCommand);
+        assert_fails("M0 0 #", 5, SvgPathError::ExpectedCommand);
+    }
+}
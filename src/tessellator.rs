@@ -0,0 +1,119 @@
+use crate::{
+    tess, DefaultVertexConstructor, Error, FillVertex, Poly, PolyBuilder, StrokeVertex,
+    VertexConstructor,
+};
+
+/// Reusable tessellation state, for rebuilding shapes without allocating every time.
+///
+/// Build into an existing `Poly` with `build_into` or `try_build_into` on any builder, starting
+/// from `Poly::default()`.
+pub struct Tessellator<C: VertexConstructor = DefaultVertexConstructor> {
+    pub(crate) fill: tess::FillTessellator,
+    pub(crate) stroke: tess::StrokeTessellator,
+    pub(crate) constructor: C,
+    // scratch space for anti-aliasing and seam fixing
+    pub(crate) fill_vertices: Vec<FillVertex>,
+    pub(crate) stroke_vertices: Vec<StrokeVertex>,
+}
+
+impl Tessellator {
+    pub fn new() -> Self {
+        Self::with_constructor(DefaultVertexConstructor)
+    }
+}
+
+impl Default for Tessellator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: VertexConstructor> Tessellator<C> {
+    /// Creates a `Tessellator` with vertices produced by `constructor`.
+    pub fn with_constructor(constructor: C) -> Self {
+        Self {
+            fill: tess::FillTessellator::new(),
+            stroke: tess::StrokeTessellator::new(),
+            constructor,
+            fill_vertices: Vec::new(),
+            stroke_vertices: Vec::new(),
+        }
+    }
+
+    pub(crate) fn try_build_into<T: PolyBuilder>(
+        &mut self,
+        poly: T,
+        out: &mut Poly<C::Vertex>,
+    ) -> Result<(), Error> {
+        let mut buf = tess::VertexBuffers {
+            vertices: std::mem::take(&mut out.vertices),
+            indices: std::mem::take(&mut out.indices),
+        };
+        buf.vertices.clear();
+        buf.indices.clear();
+        let result = Poly::tessellate(poly, self, &mut buf);
+        out.vertices = buf.vertices;
+        out.indices = buf.indices;
+        match result {
+            Ok(item) => {
+                out.fill_indices = item.fill_indices;
+                out.stroke_indices = item.stroke_indices;
+                Ok(())
+            }
+            Err(err) => {
+                // don't leave a partial shape behind
+                out.vertices.clear();
+                out.indices.clear();
+                out.fill_indices = 0..0;
+                out.stroke_indices = 0..0;
+                Err(err)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CircleBuilder, FreePolyBuilder, RoundRectBuilder};
+    use gee::{Circle, Point};
+
+    fn positions(poly: &Poly) -> Vec<(f32, f32)> {
+        poly.vertices
+            .iter()
+            .map(|vertex| (vertex.pos.x, vertex.pos.y))
+            .collect()
+    }
+
+    #[test]
+    fn error_clears_output() {
+        let mut tessellator = Tessellator::new();
+        let mut poly = Poly::default();
+        RoundRectBuilder::default()
+            .with_fill_and_stroke(2.0)
+            .build_into(&mut tessellator, &mut poly);
+        assert!(!poly.indices.is_empty());
+
+        // straight edges, so this fails in the fill tessellator instead of panicking
+        let broken = FreePolyBuilder::from_points([
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(0.0, 10.0),
+        ])
+        .with_fill_and_stroke(2.0)
+        .with_tolerance(f32::NAN);
+        assert!(broken.try_build_into(&mut tessellator, &mut poly).is_err());
+        assert!(poly.vertices.is_empty());
+        assert!(poly.indices.is_empty());
+        assert_eq!(poly.fill_indices, 0..0);
+        assert_eq!(poly.stroke_indices, 0..0);
+
+        let circle = CircleBuilder::new(Circle::from_radius(10.0)).with_stroke(1.0);
+        circle.clone().build_into(&mut tessellator, &mut poly);
+        let expected = circle.build();
+        assert_eq!(positions(&poly), positions(&expected));
+        assert_eq!(poly.indices, expected.indices);
+        assert_eq!(poly.fill_indices, expected.fill_indices);
+        assert_eq!(poly.stroke_indices, expected.stroke_indices);
+    }
+}
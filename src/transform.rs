@@ -0,0 +1,94 @@
+use crate::tess;
+use gee::{Angle, Point, Vector};
+
+/// A 2D affine transform, for rotating, scaling, skewing and translating shapes.
+///
+/// Transforms compose in the order they're applied, so
+/// `Transform::from_rotation(angle).then_translate(offset)` rotates and then translates.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transform(pub(crate) tess::math::Transform);
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Transform {
+    pub fn identity() -> Self {
+        Self(tess::math::Transform::identity())
+    }
+
+    /// Creates a transform from a row-major affine matrix, i.e.
+    /// `x' = m11 * x + m21 * y + m31` and `y' = m12 * x + m22 * y + m32`.
+    pub fn new(m11: f32, m12: f32, m21: f32, m22: f32, m31: f32, m32: f32) -> Self {
+        Self(tess::math::Transform::new(m11, m12, m21, m22, m31, m32))
+    }
+
+    pub fn from_translation(offset: Vector) -> Self {
+        Self::identity().then_translate(offset)
+    }
+
+    /// Positive angles rotate clockwise in y-down coordinates.
+    pub fn from_rotation(angle: Angle) -> Self {
+        Self::identity().then_rotate(angle)
+    }
+
+    pub fn from_scale(x: f32, y: f32) -> Self {
+        Self::identity().then_scale(x, y)
+    }
+
+    /// Skews by `x` along the x axis and by `y` along the y axis.
+    pub fn from_skew(x: Angle, y: Angle) -> Self {
+        Self::new(1.0, y.radians().tan(), x.radians().tan(), 1.0, 0.0, 0.0)
+    }
+
+    /// Applies `other` after this transform.
+    pub fn then(self, other: Self) -> Self {
+        Self(self.0.then(&other.0))
+    }
+
+    pub fn then_translate(self, offset: Vector) -> Self {
+        Self(
+            self.0
+                .then_translate(tess::math::vector(offset.dx, offset.dy)),
+        )
+    }
+
+    pub fn then_rotate(self, angle: Angle) -> Self {
+        Self(
+            self.0
+                .then_rotate(tess::math::Angle::radians(angle.radians())),
+        )
+    }
+
+    pub fn then_scale(self, x: f32, y: f32) -> Self {
+        Self(self.0.then_scale(x, y))
+    }
+
+    pub fn then_skew(self, x: Angle, y: Angle) -> Self {
+        self.then(Self::from_skew(x, y))
+    }
+
+    /// Returns `None` if this transform collapses everything onto a line or a point.
+    pub fn inverse(&self) -> Option<Self> {
+        self.0.inverse().map(Self)
+    }
+
+    pub fn transform_point(&self, point: Point) -> Point {
+        self.0.transform_point(point.into()).into()
+    }
+
+    /// How much this transform scales lengths, in the worst case.
+    ///
+    /// Useful for getting the `view_scale` for `with_screen_tolerance` from a view transform.
+    pub fn max_scale(&self) -> f32 {
+        // the largest singular value of the linear part
+        let tess::math::Transform {
+            m11, m12, m21, m22, ..
+        } = self.0;
+        let sum = m11 * m11 + m12 * m12 + m21 * m21 + m22 * m22;
+        let det = m11 * m22 - m12 * m21;
+        ((sum + (sum * sum - 4.0 * det * det).max(0.0).sqrt()) / 2.0).sqrt()
+    }
+}
@@ -0,0 +1,66 @@
+use crate::InvalidInput;
+use gee::{Angle, Circle, Point, Rect};
+
+pub(crate) fn finite(name: &'static str, value: f32) -> Result<(), InvalidInput> {
+    if value.is_finite() {
+        Ok(())
+    } else {
+        Err(InvalidInput::NonFinite { name, value })
+    }
+}
+
+pub(crate) fn non_negative(name: &'static str, value: f32) -> Result<(), InvalidInput> {
+    finite(name, value)?;
+    if value >= 0.0 {
+        Ok(())
+    } else {
+        Err(InvalidInput::Negative { name, value })
+    }
+}
+
+pub(crate) fn positive(name: &'static str, value: f32) -> Result<(), InvalidInput> {
+    finite(name, value)?;
+    if value > 0.0 {
+        Ok(())
+    } else {
+        Err(InvalidInput::OutOfRange {
+            name,
+            value,
+            expected: "positive",
+        })
+    }
+}
+
+pub(crate) fn angle(name: &'static str, angle: Angle) -> Result<(), InvalidInput> {
+    finite(name, angle.radians())
+}
+
+pub(crate) fn point(name: &'static str, point: Point) -> Result<(), InvalidInput> {
+    finite(name, point.x)?;
+    finite(name, point.y)
+}
+
+pub(crate) fn circle(circle: Circle) -> Result<(), InvalidInput> {
+    point("center", circle.center())?;
+    non_negative("radius", circle.radius())
+}
+
+/// Checks an inner radius, which must leave a ring of some width inside the outer `radius`.
+pub(crate) fn inner_radius(inner_radius: f32, radius: f32) -> Result<(), InvalidInput> {
+    non_negative("inner_radius", inner_radius)?;
+    if inner_radius < radius {
+        Ok(())
+    } else {
+        Err(InvalidInput::OutOfRange {
+            name: "inner_radius",
+            value: inner_radius,
+            expected: "less than the radius",
+        })
+    }
+}
+
+pub(crate) fn rect(rect: Rect) -> Result<(), InvalidInput> {
+    point("rect", rect.top_left())?;
+    non_negative("width", rect.size().width)?;
+    non_negative("height", rect.size().height)
+}
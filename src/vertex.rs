@@ -1,5 +1,9 @@
-use crate::{tess, StrokeOptions};
+use crate::{
+    default_start_angle, fringe::FringeSource, paint::Painter, tess, Color, StrokeOptions,
+    Transform,
+};
 use gee::{Point, Rect, Vector};
+use std::{cell::Cell, collections::HashMap, rc::Rc};
 
 #[derive(Copy, Clone, Debug)]
 pub struct Vertex {
@@ -7,51 +11,376 @@ pub struct Vertex {
     pub tex_coord: Point,
 }
 
-pub(crate) struct FillVertexConstructor {
-    // cached version of gee::Rect in the format we want
-    top_left: Point,
-    scale: Vector,
+/// A `Vertex` with a color, as produced by `ColoredVertexConstructor`.
+#[derive(Copy, Clone, Debug)]
+pub struct ColoredVertex {
+    pub pos: Point,
+    pub tex_coord: Point,
+    pub color: Color,
+}
+
+/// Which side of the path a stroke vertex is on, relative to the path's direction.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+/// Everything pendragon knows about a vertex produced by filling.
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub struct FillVertex {
+    pub pos: Point,
+    pub tex_coord: Point,
+    /// The color from the builder's `Paint`, or white if there isn't one.
+    pub color: Color,
+    /// 1 inside the shape, and 0 on the outer edge of the anti-aliasing fringe.
+    pub coverage: f32,
+}
+
+/// Everything pendragon knows about a vertex produced by stroking.
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub struct StrokeVertex {
+    pub pos: Point,
+    pub tex_coord: Point,
+    /// The color from the builder's `Paint`, or white if there isn't one.
+    pub color: Color,
+    /// 1 inside the shape, and 0 on the outer edge of the anti-aliasing fringe.
+    pub coverage: f32,
+    /// The point on the path this vertex was extruded from.
+    pub position_on_path: Point,
+    /// The direction this vertex was extruded in.
+    ///
+    /// Displacing the vertex along this inflates the stroke by 1 on each side.
+    pub normal: Vector,
+    pub side: Side,
+    /// The distance along the path, including any gaps between dashes.
+    pub advancement: f32,
+}
+
+/// Vertices with a position that `Poly::transform` can move.
+pub trait Positioned {
+    fn pos_mut(&mut self) -> &mut Point;
+}
+
+impl Positioned for Vertex {
+    fn pos_mut(&mut self) -> &mut Point {
+        &mut self.pos
+    }
+}
+
+impl Positioned for ColoredVertex {
+    fn pos_mut(&mut self) -> &mut Point {
+        &mut self.pos
+    }
+}
+
+/// Converts the vertices pendragon produces into your own vertex type.
+pub trait VertexConstructor {
+    type Vertex;
+
+    fn new_fill_vertex(&mut self, vertex: FillVertex) -> Self::Vertex;
+
+    fn new_stroke_vertex(&mut self, vertex: StrokeVertex) -> Self::Vertex;
+}
+
+/// Produces `Vertex`, which is what you get from `build` and `try_build`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultVertexConstructor;
+
+impl VertexConstructor for DefaultVertexConstructor {
+    type Vertex = Vertex;
+
+    fn new_fill_vertex(&mut self, vertex: FillVertex) -> Vertex {
+        Vertex {
+            pos: vertex.pos,
+            tex_coord: vertex.tex_coord,
+        }
+    }
+
+    fn new_stroke_vertex(&mut self, vertex: StrokeVertex) -> Vertex {
+        Vertex {
+            pos: vertex.pos,
+            tex_coord: vertex.tex_coord,
+        }
+    }
+}
+
+/// Produces `ColoredVertex`, for use with `Paint`.
+///
+/// Anti-aliasing coverage is multiplied into the alpha channel.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ColoredVertexConstructor;
+
+impl VertexConstructor for ColoredVertexConstructor {
+    type Vertex = ColoredVertex;
+
+    fn new_fill_vertex(&mut self, vertex: FillVertex) -> ColoredVertex {
+        let [r, g, b, a] = vertex.color;
+        ColoredVertex {
+            pos: vertex.pos,
+            tex_coord: vertex.tex_coord,
+            color: [r, g, b, a * vertex.coverage],
+        }
+    }
+
+    fn new_stroke_vertex(&mut self, vertex: StrokeVertex) -> ColoredVertex {
+        let [r, g, b, a] = vertex.color;
+        ColoredVertex {
+            pos: vertex.pos,
+            tex_coord: vertex.tex_coord,
+            color: [r, g, b, a * vertex.coverage],
+        }
+    }
 }
 
-impl FillVertexConstructor {
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum FillTexCoords {
+    Rect {
+        // cached version of gee::Rect in the format we want
+        top_left: Point,
+        scale: Vector,
+    },
+    /// `x` is the angle clockwise from north and `y` is the distance from `inner_radius`, both
+    /// normalized to the range [0, 1].
+    Polar {
+        center: Point,
+        inner_radius: f32,
+        radial_scale: f32,
+    },
+}
+
+impl FillTexCoords {
     pub fn new(bounding_box: Rect) -> Self {
-        Self {
+        Self::Rect {
             top_left: bounding_box.top_left(),
             scale: bounding_box.size().to_vector().map(f32::recip),
         }
     }
+
+    pub fn polar(center: Point, inner_radius: f32, outer_radius: f32) -> Self {
+        Self::Polar {
+            center,
+            inner_radius,
+            radial_scale: (outer_radius - inner_radius).recip(),
+        }
+    }
+
+    fn tex_coord(&self, pos: Point) -> Point {
+        match *self {
+            Self::Rect { top_left, scale } => (pos - top_left).to_point() * scale,
+            Self::Polar {
+                center,
+                inner_radius,
+                radial_scale,
+            } => {
+                let offset = tess::math::Point::from(pos) - tess::math::Point::from(center);
+                let angle = offset.angle_from_x_axis().radians - default_start_angle().radians();
+                Point::new(
+                    (angle / std::f32::consts::TAU).rem_euclid(1.0),
+                    (offset.length() - inner_radius) * radial_scale,
+                )
+            }
+        }
+    }
+}
+
+pub(crate) struct FillVertexConstructor<'a, C> {
+    // maps back from the transformed path, so texture coordinates follow the shape
+    local: tess::math::Transform,
+    tex_coords: FillTexCoords,
+    painter: Painter,
+    constructor: &'a mut C,
+    // only kept around when we need to fix a seam or add a fringe
+    record: bool,
+    vertices: &'a mut Vec<FillVertex>,
+}
+
+impl<'a, C: VertexConstructor> FillVertexConstructor<'a, C> {
+    pub fn new(
+        local: Transform,
+        tex_coords: FillTexCoords,
+        painter: Painter,
+        anti_aliasing: bool,
+        constructor: &'a mut C,
+        vertices: &'a mut Vec<FillVertex>,
+    ) -> Self {
+        vertices.clear();
+        Self {
+            local: local.0,
+            tex_coords,
+            painter,
+            constructor,
+            record: anti_aliasing || matches!(tex_coords, FillTexCoords::Polar { .. }),
+            vertices,
+        }
+    }
+
+    /// Polar texture coordinates wrap around from 1 to 0, so triangles straddling north need
+    /// their own copies of the vertices on the 0 side, shifted up to be past 1.
+    ///
+    /// Note: this assumes the texture will use a tiling Sampler.
+    pub fn fix_seam(
+        &mut self,
+        buf: &mut tess::VertexBuffers<C::Vertex, u32>,
+        first_vertex: usize,
+        first_index: usize,
+    ) {
+        if let FillTexCoords::Polar { .. } = self.tex_coords {
+            let mut wrapped = HashMap::new();
+            for triangle in buf.indices[first_index..].chunks_exact_mut(3) {
+                let vertex = |index: u32| self.vertices[index as usize - first_vertex];
+                let (min, max) =
+                    triangle
+                        .iter()
+                        .fold((f32::MAX, f32::MIN), |(min, max), &index| {
+                            let x = vertex(index).tex_coord.x;
+                            (min.min(x), max.max(x))
+                        });
+                if max - min <= 0.5 {
+                    continue;
+                }
+                for index in triangle {
+                    let mut vertex = vertex(*index);
+                    if vertex.tex_coord.x < 0.5 {
+                        *index = *wrapped.entry(*index).or_insert_with(|| {
+                            vertex.tex_coord.x += 1.0;
+                            buf.vertices.push(self.constructor.new_fill_vertex(vertex));
+                            buf.vertices.len() as u32 - 1
+                        });
+                    }
+                }
+            }
+        }
+    }
 }
 
-impl tess::FillVertexConstructor<Vertex> for FillVertexConstructor {
-    fn new_vertex(&mut self, vertex: tess::FillVertex) -> Vertex {
+impl<'a, 'b, C: VertexConstructor> tess::FillVertexConstructor<C::Vertex>
+    for &'b mut FillVertexConstructor<'a, C>
+{
+    fn new_vertex(&mut self, vertex: tess::FillVertex) -> C::Vertex {
         let pos = Point::from(vertex.position());
-        let tex_coord = (pos - self.top_left).to_point() * self.scale;
-        Vertex { pos, tex_coord }
+        let local = self.local.transform_point(vertex.position()).into();
+        let vertex = FillVertex {
+            pos,
+            tex_coord: self.tex_coords.tex_coord(local),
+            color: self.painter.color(local),
+            coverage: 1.0,
+        };
+        if self.record {
+            self.vertices.push(vertex);
+        }
+        self.constructor.new_fill_vertex(vertex)
+    }
+}
+
+impl<'a, C: VertexConstructor> FringeSource for FillVertexConstructor<'a, C> {
+    type Vertex = C::Vertex;
+
+    fn position(&self, nth: usize) -> Point {
+        self.vertices[nth].pos
+    }
+
+    fn fringe_vertex(&mut self, _nth: usize, pos: Point) -> C::Vertex {
+        let local = self.local.transform_point(pos.into()).into();
+        let vertex = FillVertex {
+            pos,
+            tex_coord: self.tex_coords.tex_coord(local),
+            color: self.painter.color(local),
+            coverage: 0.0,
+        };
+        self.vertices.push(vertex);
+        self.constructor.new_fill_vertex(vertex)
     }
 }
 
-pub(crate) struct StrokeVertexConstructor {
+pub(crate) struct StrokeVertexConstructor<'a, C> {
+    // maps back from the transformed path, so `Paint` follows the shape
+    local: tess::math::Transform,
     options: StrokeOptions,
+    painter: Painter,
+    // added to lyon's advancement, which doesn't include the gaps between dashes
+    advancement_offset: Rc<Cell<f32>>,
+    constructor: &'a mut C,
+    // only kept around when we need to add a fringe
+    record: bool,
+    vertices: &'a mut Vec<StrokeVertex>,
 }
 
-impl StrokeVertexConstructor {
-    pub fn new(options: StrokeOptions) -> Self {
-        Self { options }
+impl<'a, C> StrokeVertexConstructor<'a, C> {
+    pub fn new(
+        local: Transform,
+        options: StrokeOptions,
+        painter: Painter,
+        advancement_offset: Rc<Cell<f32>>,
+        anti_aliasing: bool,
+        constructor: &'a mut C,
+        vertices: &'a mut Vec<StrokeVertex>,
+    ) -> Self {
+        vertices.clear();
+        Self {
+            local: local.0,
+            options,
+            painter,
+            advancement_offset,
+            constructor,
+            record: anti_aliasing,
+            vertices,
+        }
     }
 }
 
-impl tess::StrokeVertexConstructor<Vertex> for StrokeVertexConstructor {
-    fn new_vertex(&mut self, vertex: tess::StrokeVertex) -> Vertex {
-        Vertex {
-            pos: vertex.position().into(),
+impl<'a, 'b, C: VertexConstructor> tess::StrokeVertexConstructor<C::Vertex>
+    for &'b mut StrokeVertexConstructor<'a, C>
+{
+    fn new_vertex(&mut self, vertex: tess::StrokeVertex) -> C::Vertex {
+        let side = match vertex.side() {
+            tess::Side::Left => Side::Left,
+            tess::Side::Right => Side::Right,
+        };
+        let advancement = vertex.advancement() + self.advancement_offset.get();
+        let normal = vertex.normal();
+        let pos = vertex.position().into();
+        let vertex = StrokeVertex {
+            pos,
             tex_coord: Point::new(
-                match vertex.side() {
-                    tess::Side::Left => 1.0,
-                    tess::Side::Right => 0.0,
+                match side {
+                    Side::Left => 1.0,
+                    Side::Right => 0.0,
                 },
-                vertex.advancement() / self.options.stroke_width
-                    * self.options.texture_aspect_ratio,
+                advancement / self.options.stroke_width * self.options.texture_aspect_ratio,
             ),
+            color: self
+                .painter
+                .color(self.local.transform_point(vertex.position()).into()),
+            coverage: 1.0,
+            position_on_path: vertex.position_on_path().into(),
+            normal: Vector::new(normal.x, normal.y),
+            side,
+            advancement,
+        };
+        if self.record {
+            self.vertices.push(vertex);
         }
+        self.constructor.new_stroke_vertex(vertex)
+    }
+}
+
+impl<'a, C: VertexConstructor> FringeSource for StrokeVertexConstructor<'a, C> {
+    type Vertex = C::Vertex;
+
+    fn position(&self, nth: usize) -> Point {
+        self.vertices[nth].pos
+    }
+
+    fn fringe_vertex(&mut self, nth: usize, pos: Point) -> C::Vertex {
+        let vertex = StrokeVertex {
+            pos,
+            coverage: 0.0,
+            ..self.vertices[nth]
+        };
+        self.vertices.push(vertex);
+        self.constructor.new_stroke_vertex(vertex)
     }
 }